@@ -0,0 +1,179 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{Unauthorized, UnknownOffer};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{Config, CONFIG};
+use atomic_swaps_contract::msg::{
+    ContractOffer, ExecuteMsg as SwapExecuteMsg, FillSettlement, MakerAsset,
+    QueryMsg as SwapQueryMsg, Quote,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: info.sender,
+            swap_contract: deps.api.addr_validate(&msg.swap_contract)?,
+            price: msg.price,
+            max_fill: msg.max_fill,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    match msg {
+        ExecuteMsg::ConfirmFill {
+            offer_id,
+            taker,
+            maker_amount,
+            ..
+        } => {
+            if info.sender != config.swap_contract {
+                return Err(Unauthorized {});
+            }
+            let offer = load_offer(deps.as_ref(), &env, &config, &offer_id)?;
+
+            // Escrowed fills were already paid by the swap contract
+            let mut response = Response::new().add_attribute("action", "confirm_fill");
+            if offer.settlement == (FillSettlement::MakerTransfer {}) {
+                response =
+                    response.add_message(transfer_msg(&offer.maker_asset, &taker, maker_amount)?);
+            }
+            Ok(response.add_attribute("offer_id", offer_id))
+        }
+        _ if info.sender != config.owner => Err(Unauthorized {}),
+        ExecuteMsg::MakeOffer {
+            maker_asset,
+            taker_denom,
+            settlement,
+        } => Ok(Response::new()
+            .add_message(WasmMsg::Execute {
+                contract_addr: config.swap_contract.to_string(),
+                msg: to_binary(&SwapExecuteMsg::MakeContractOffer {
+                    maker_asset,
+                    taker_denom,
+                    settlement,
+                })?,
+                funds: info.funds,
+            })
+            .add_attribute("action", "make_offer")),
+        ExecuteMsg::UpdateQuote { price, max_fill } => {
+            config.price = price;
+            config.max_fill = max_fill;
+            CONFIG.save(deps.storage, &config)?;
+            Ok(Response::new()
+                .add_attribute("action", "update_quote")
+                .add_attribute("price", price.to_string())
+                .add_attribute("max_fill", max_fill))
+        }
+        ExecuteMsg::Withdraw { amount } => Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: config.owner.to_string(),
+                amount,
+            })
+            .add_attribute("action", "withdraw")),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    match msg {
+        QueryMsg::QuoteRequest { offer_id, .. } => {
+            let offer = load_offer(deps, &env, &config, &offer_id)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+            // Makers paying fills themselves can't sell more than they hold
+            let max_maker_amount = match offer.settlement {
+                FillSettlement::Escrow {} => config.max_fill,
+                FillSettlement::MakerTransfer {} => {
+                    config
+                        .max_fill
+                        .min(balance(deps, &env, &offer.maker_asset)?)
+                }
+            };
+            to_binary(&Quote {
+                price: config.price,
+                max_maker_amount,
+            })
+        }
+        QueryMsg::Config {} => to_binary(&config),
+    }
+}
+
+fn load_offer(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    offer_id: &str,
+) -> Result<ContractOffer, ContractError> {
+    let offer: Option<ContractOffer> = deps.querier.query_wasm_smart(
+        &config.swap_contract,
+        &SwapQueryMsg::ContractOffer {
+            offer_id: offer_id.to_string(),
+        },
+    )?;
+    offer
+        .filter(|offer| offer.maker == env.contract.address)
+        .ok_or_else(|| UnknownOffer {
+            offer_id: offer_id.to_string(),
+        })
+}
+
+fn balance(deps: Deps, env: &Env, asset: &MakerAsset) -> StdResult<Uint128> {
+    match asset {
+        MakerAsset::Native { denom } => Ok(deps
+            .querier
+            .query_balance(&env.contract.address, denom)?
+            .amount),
+        MakerAsset::Cw20 { contract } => {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                contract,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(res.balance)
+        }
+    }
+}
+
+fn transfer_msg(asset: &MakerAsset, to: &str, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match asset {
+        MakerAsset::Native { denom } => BankMsg::Send {
+            to_address: to.to_string(),
+            amount: vec![Coin::new(amount.u128(), denom)],
+        }
+        .into(),
+        MakerAsset::Cw20 { contract } => WasmMsg::Execute {
+            contract_addr: contract.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
@@ -0,0 +1,45 @@
+use crate::state::Config;
+use atomic_swaps_contract::msg::{FillSettlement, MakerAsset, Quote};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, Decimal, Uint128};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub swap_contract: String,
+    pub price: Decimal,
+    pub max_fill: Uint128,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Owner only. Makes a contract offer on the swap contract, escrowing the funds sent
+    MakeOffer {
+        maker_asset: MakerAsset,
+        taker_denom: String,
+        settlement: FillSettlement,
+    },
+    /// Owner only. Changes the quote of every offer
+    UpdateQuote { price: Decimal, max_fill: Uint128 },
+    /// Owner only. Withdraws proceeds or inventory held by the maker
+    Withdraw { amount: Vec<Coin> },
+    /// Swap contract only. Pays the taker of a `MakerTransfer` offer.
+    ConfirmFill {
+        offer_id: String,
+        taker: String,
+        maker_amount: Uint128,
+        taker_coin: Coin,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Quote)]
+    QuoteRequest {
+        offer_id: String,
+        taker: String,
+        taker_coin: Coin,
+    },
+    #[returns(Config)]
+    Config {},
+}
@@ -0,0 +1,15 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::Item;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: Addr,
+    pub swap_contract: Addr,
+    /// Maker asset paid per unit of the taker coin
+    pub price: Decimal,
+    /// Most of the maker asset sold in a single fill
+    pub max_fill: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
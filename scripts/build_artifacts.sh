@@ -0,0 +1,42 @@
+#!/usr/bin/env bash
+# Builds the wasm artifacts the integration tests load from artifacts/:
+#   atomic_swaps_contract.wasm and jit_maker.wasm from this workspace,
+#   v1/atomic_swaps_contract.wasm from the pre-cw2 v1 release, for the migration tests,
+#   cw4_group.wasm and cw20_base.wasm from the cw-plus release, for the access gate and holding
+#   requirement tests,
+#   cw721_base.wasm from the cw-nfts release, for the holding requirement tests.
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+OPTIMIZER_VERSION="${OPTIMIZER_VERSION:-0.14.0}"
+# v1 release, before cw2 versioning and the migrate entry point
+V1_REF="${V1_REF:-v0.1.0}"
+CW_PLUS_VERSION="${CW_PLUS_VERSION:-v1.1.0}"
+CW_NFTS_VERSION="${CW_NFTS_VERSION:-v0.18.0}"
+
+# Optimizes the contract at `path` within `dir`, writing it to `dir`/artifacts
+optimize() {
+  local dir="$1" path="$2"
+  docker run --rm -v "$dir":/code \
+    --mount type=volume,source="$(basename "$dir")_cache",target=/target \
+    --mount type=volume,source=registry_cache,target=/usr/local/cargo/registry \
+    "cosmwasm/rust-optimizer:$OPTIMIZER_VERSION" "$path"
+}
+
+cd "$ROOT"
+optimize "$ROOT" .
+optimize "$ROOT" ./contracts/jit-maker
+
+V1_DIR="$(mktemp -d)"
+trap 'git -C "$ROOT" worktree remove --force "$V1_DIR"' EXIT
+git worktree add --detach "$V1_DIR" "$V1_REF"
+optimize "$V1_DIR" .
+mkdir -p artifacts/v1
+cp "$V1_DIR/artifacts/atomic_swaps_contract.wasm" artifacts/v1/
+
+for contract in cw4_group cw20_base; do
+  curl -sSfL -o "artifacts/$contract.wasm" \
+    "https://github.com/CosmWasm/cw-plus/releases/download/$CW_PLUS_VERSION/$contract.wasm"
+done
+curl -sSfL -o artifacts/cw721_base.wasm \
+  "https://github.com/CosmWasm/cw-nfts/releases/download/$CW_NFTS_VERSION/cw721_base.wasm"
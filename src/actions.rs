@@ -0,0 +1,199 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{ActionContractNotApproved, InvalidProceedsAction};
+use crate::execute::assert_owner;
+use crate::ibc::{track_ibc_transfer, transfer_msg, validate_ibc_receiver};
+use crate::msg::{IbcReceiver, IbcTransfer, IbcTransferStatus, ProceedsAction};
+use crate::state::{
+    PendingAction, ACTION_CONTRACTS, APPROVED_IBC_CHANNELS, PENDING_ACTION,
+    REQUIRE_APPROVED_IBC_CHANNELS,
+};
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    Storage, SubMsg, SubMsgResult, WasmMsg,
+};
+use osmosis_std::types::cosmos::authz::v1beta1::MsgExec;
+use osmosis_std::types::cosmos::staking::v1beta1::MsgDelegate;
+
+pub const PROCEEDS_ACTION_REPLY_ID: u64 = 3;
+
+fn render_msg(template: &str, offer_id: &str, maker: &str, proceeds: &Coin) -> String {
+    template
+        .replace("{{offer_id}}", offer_id)
+        .replace("{{maker}}", maker)
+        .replace("{{amount}}", &proceeds.amount.to_string())
+        .replace("{{denom}}", &proceeds.denom)
+}
+
+// Actions stay usable only while their contract or channel is approved
+fn is_approved(storage: &dyn Storage, action: &ProceedsAction) -> StdResult<bool> {
+    Ok(match action {
+        ProceedsAction::WasmExecute { contract, .. } => {
+            ACTION_CONTRACTS.has(storage, &Addr::unchecked(contract))
+        }
+        ProceedsAction::IbcTransfer { channel, .. } => {
+            !REQUIRE_APPROVED_IBC_CHANNELS
+                .may_load(storage)?
+                .unwrap_or_default()
+                || APPROVED_IBC_CHANNELS.has(storage, channel)
+        }
+        ProceedsAction::Delegate { .. } => true,
+    })
+}
+
+pub fn validate_proceeds_action(
+    deps: &DepsMut,
+    action: &ProceedsAction,
+) -> Result<(), ContractError> {
+    let invalid = |reason: String| InvalidProceedsAction { reason };
+
+    match action {
+        ProceedsAction::WasmExecute { contract, msg } => {
+            deps.api.addr_validate(contract)?;
+            // Placeholders may stand in for strings or numbers, so the template is checked with
+            // sample values filled in
+            let sample = render_msg(msg, "1", contract, &Coin::new(1, "denom"));
+            if serde_json::from_str::<serde_json::Value>(&sample).is_err() {
+                return Err(invalid("msg is not a valid JSON template".to_string()));
+            }
+            if !is_approved(deps.storage, action)? {
+                return Err(ActionContractNotApproved {
+                    contract: contract.clone(),
+                });
+            }
+        }
+        ProceedsAction::IbcTransfer { channel, receiver } => {
+            validate_ibc_receiver(deps, channel.clone(), receiver.clone(), None)?;
+            if !is_approved(deps.storage, action)? {
+                return Err(invalid(format!("{channel} is not an approved channel")));
+            }
+        }
+        ProceedsAction::Delegate { validator } => {
+            if validator.is_empty() {
+                return Err(invalid("validator cannot be empty".to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Return transfers are tracked by offer id, so proceeds transfers get an id of their own
+fn proceeds_transfer(pending: &PendingAction, channel: &str, receiver: &str) -> IbcTransfer {
+    IbcTransfer {
+        offer_id: format!("{}-proceeds", pending.offer_id),
+        receiver: IbcReceiver {
+            channel: channel.to_string(),
+            receiver: receiver.to_string(),
+            recovery_addr: Some(pending.maker.clone()),
+        },
+        coin: pending.proceeds.clone().into(),
+        attempts: 1,
+        status: IbcTransferStatus::Pending { sequence: 0 },
+    }
+}
+
+// Messages putting the maker's proceeds to use. The action is replied to, so the proceeds can be
+// refunded to the maker if it fails. Actions no longer approved fall back to paying the maker.
+pub fn proceeds_action_msgs(
+    storage: &mut dyn Storage,
+    env: &Env,
+    pending: PendingAction,
+) -> Result<Vec<SubMsg>, ContractError> {
+    if !is_approved(storage, &pending.action)? {
+        return Ok(vec![SubMsg::new(BankMsg::Send {
+            to_address: pending.maker,
+            amount: vec![pending.proceeds],
+        })]);
+    }
+
+    let mut msgs = vec![];
+    let action_msg: CosmosMsg = match &pending.action {
+        ProceedsAction::WasmExecute { contract, msg } => WasmMsg::Execute {
+            contract_addr: contract.clone(),
+            msg: Binary::from(
+                render_msg(msg, &pending.offer_id, &pending.maker, &pending.proceeds).as_bytes(),
+            ),
+            funds: vec![pending.proceeds.clone()],
+        }
+        .into(),
+        ProceedsAction::IbcTransfer { channel, receiver } => {
+            transfer_msg(env, &proceeds_transfer(&pending, channel, receiver))
+        }
+        ProceedsAction::Delegate { validator } => {
+            // Delegations can only be made from the maker's account, so the proceeds are paid to
+            // the maker first
+            msgs.push(SubMsg::new(BankMsg::Send {
+                to_address: pending.maker.clone(),
+                amount: vec![pending.proceeds.clone()],
+            }));
+            let exec_msg = MsgExec {
+                grantee: env.contract.address.to_string(),
+                msgs: vec![MsgDelegate {
+                    delegator_address: pending.maker.clone(),
+                    validator_address: validator.clone(),
+                    amount: Some(pending.proceeds.clone().into()),
+                }
+                .to_any()],
+            };
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+                value: Binary::from(exec_msg),
+            }
+        }
+    };
+    msgs.push(SubMsg::reply_always(action_msg, PROCEEDS_ACTION_REPLY_ID));
+
+    PENDING_ACTION.save(storage, &pending)?;
+    Ok(msgs)
+}
+
+pub fn handle_proceeds_action_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let pending = PENDING_ACTION.load(deps.storage)?;
+    PENDING_ACTION.remove(deps.storage);
+
+    let response = Response::new().add_attribute("proceeds_action", pending.action.as_str());
+    match msg.result {
+        SubMsgResult::Ok(res) => {
+            if let ProceedsAction::IbcTransfer { channel, receiver } = &pending.action {
+                // Failed acks and timeouts are retried, then refunded to the maker
+                let transfer = proceeds_transfer(&pending, channel, receiver);
+                track_ibc_transfer(deps.storage, transfer, res.data)?;
+            }
+            Ok(response.add_attribute("proceeds_action_executed", pending.offer_id))
+        }
+        SubMsgResult::Err(err) => {
+            let response = response
+                .add_attribute("proceeds_action_failed", pending.offer_id)
+                .add_attribute("error", err);
+            // Failed delegations leave the proceeds with the maker already
+            Ok(match pending.action {
+                ProceedsAction::Delegate { .. } => response,
+                _ => response.add_message(BankMsg::Send {
+                    to_address: pending.maker,
+                    amount: vec![pending.proceeds],
+                }),
+            })
+        }
+    }
+}
+
+pub fn update_action_contracts(
+    deps: DepsMut,
+    info: MessageInfo,
+    approve: Vec<String>,
+    revoke: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    for contract in &approve {
+        let contract = deps.api.addr_validate(contract)?;
+        ACTION_CONTRACTS.save(deps.storage, &contract, &true)?;
+    }
+    for contract in &revoke {
+        ACTION_CONTRACTS.remove(deps.storage, &Addr::unchecked(contract));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_action_contracts")
+        .add_attribute("approved", approve.join(","))
+        .add_attribute("revoked", revoke.join(",")))
+}
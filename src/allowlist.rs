@@ -0,0 +1,70 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    AllowlistCapExceeded, InvalidMerkleProof, InvalidMerkleRoot, NotOnAllowlist,
+};
+use crate::msg::{AllowlistProof, TakerAllowlist};
+use crate::state::ALLOWLIST_TAKEN;
+use cosmwasm_std::{Addr, Storage, Uint128};
+use sha2::{Digest, Sha256};
+
+// Roots are stored in lowercase, so offers sharing an allowlist share its fill accounting
+pub fn validate_allowlist(allowlist: TakerAllowlist) -> Result<TakerAllowlist, ContractError> {
+    decode_hash(&allowlist.merkle_root).ok_or(InvalidMerkleRoot {})?;
+    Ok(TakerAllowlist {
+        merkle_root: allowlist.merkle_root.to_lowercase(),
+        ..allowlist
+    })
+}
+
+// Checks the taker's proof against the offer's allowlist and counts the maker coin amount taken
+// against the taker's cap
+pub fn take_from_allowlist(
+    storage: &mut dyn Storage,
+    allowlist: &TakerAllowlist,
+    taker: &Addr,
+    proof: Option<AllowlistProof>,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let proof = proof.ok_or(NotOnAllowlist {})?;
+    let leaf = match (allowlist.per_address_caps, proof.cap) {
+        (true, Some(cap)) => format!("{taker}{cap}"),
+        (false, None) => taker.to_string(),
+        _ => return Err(NotOnAllowlist {}),
+    };
+
+    let mut hash: [u8; 32] = Sha256::digest(leaf.as_bytes()).into();
+    for sibling in &proof.proof {
+        let sibling = decode_hash(sibling).ok_or(InvalidMerkleProof {})?;
+        // Pairs are hashed in sorted order, so proofs don't need to say which side a sibling is on
+        let (left, right) = match hash <= sibling {
+            true => (hash, sibling),
+            false => (sibling, hash),
+        };
+        hash = Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into();
+    }
+    if decode_hash(&allowlist.merkle_root) != Some(hash) {
+        return Err(NotOnAllowlist {});
+    }
+
+    let key = (allowlist.merkle_root.as_str(), taker);
+    let taken = ALLOWLIST_TAKEN.may_load(storage, key)?.unwrap_or_default() + amount;
+    if let Some(cap) = proof.cap {
+        if taken > cap {
+            return Err(AllowlistCapExceeded {
+                cap,
+                taken: taken - amount,
+            });
+        }
+    }
+    ALLOWLIST_TAKEN.save(storage, key, &taken)?;
+
+    Ok(())
+}
+
+fn decode_hash(hash: &str) -> Option<[u8; 32]> {
+    hex::decode(hash).ok()?.try_into().ok()
+}
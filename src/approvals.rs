@@ -0,0 +1,199 @@
+use crate::callbacks::assert_no_flash_in_progress;
+use crate::counters::load_open_offer;
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    AlreadyApplied, ApplicationLapsed, InaccurateFunds, InvalidApplication, NoApplicationFound,
+    NoOfferFound, NotOfferMaker, TooManyApplicants,
+};
+use crate::maker_bonds::settlement_msg;
+use crate::migrate::assert_not_migrating;
+use crate::msg::{Application, Offer, Referral, TakerCallback};
+use crate::state::{APPLICATIONS, IBC_TAKERS, OFFERS};
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+};
+
+// Keeps refunding every applicant of an offer within a bounded number of bank messages
+pub const MAX_APPLICANTS: u32 = 20;
+
+fn refund_msg(application: &Application) -> StdResult<BankMsg> {
+    Ok(BankMsg::Send {
+        to_address: application.applicant.clone(),
+        amount: vec![Coin::try_from(application.taker_coin.clone())?],
+    })
+}
+
+// Removes every pending applicant of an offer, returning their refunds
+pub fn refund_applicants(storage: &mut dyn Storage, offer_id: &str) -> StdResult<Vec<BankMsg>> {
+    let applications = APPLICATIONS
+        .prefix(offer_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    applications
+        .into_iter()
+        .map(|(applicant, application)| {
+            APPLICATIONS.remove(storage, (offer_id, &applicant));
+            refund_msg(&application)
+        })
+        .collect()
+}
+
+// Takers of approval offers escrow the taker coin and wait for the maker instead of settling
+#[allow(clippy::too_many_arguments)]
+pub fn apply(
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    offer_id: &str,
+    offer: &Offer,
+    approval_window: u64,
+    referral: Option<Referral>,
+    recipient: Option<Addr>,
+    callback: Option<TakerCallback>,
+) -> Result<Response, ContractError> {
+    // Applicants are refunded on Osmosis, and callbacks need the settlement to run right away
+    if IBC_TAKERS.has(storage, offer_id) || callback.is_some() {
+        return Err(InvalidApplication {
+            reason: "approval offers can only be applied to directly".to_string(),
+        });
+    }
+    if APPLICATIONS.has(storage, (offer_id, &info.sender)) {
+        return Err(AlreadyApplied {});
+    }
+    let applicants = APPLICATIONS
+        .prefix(offer_id)
+        .keys_raw(storage, None, None, Order::Ascending)
+        .count();
+    if applicants >= MAX_APPLICANTS as usize {
+        return Err(TooManyApplicants {
+            max: MAX_APPLICANTS,
+        });
+    }
+
+    if info.funds.len() != 1 || info.funds[0] != Coin::try_from(offer.taker_coin.clone())? {
+        return Err(InaccurateFunds {});
+    }
+
+    let lapses_at = env.block.time.plus_seconds(approval_window);
+    APPLICATIONS.save(
+        storage,
+        (offer_id, &info.sender),
+        &Application {
+            applicant: info.sender.to_string(),
+            taker_coin: offer.taker_coin.clone(),
+            applied_at: env.block.time,
+            lapses_at,
+            referral,
+            recipient: recipient.as_ref().map(Addr::to_string),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "apply")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("applicant", &info.sender)
+        .add_attribute("lapses_at", lapses_at.seconds().to_string()))
+}
+
+// Settles the offer with the approved applicant. Fulfilment refunds everyone else.
+pub fn approve_applicant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    applicant: String,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    assert_no_flash_in_progress(deps.storage)?;
+
+    // Expired offers can't settle, even though their applicants are only refunded once pruned
+    let mut offer = load_open_offer(deps.storage, &env, &offer_id)?;
+    if offer.maker != info.sender {
+        return Err(NotOfferMaker {});
+    }
+
+    let applicant = deps.api.addr_validate(&applicant)?;
+    let application = APPLICATIONS
+        .may_load(deps.storage, (&offer_id, &applicant))?
+        .ok_or(NoApplicationFound {})?;
+    if env.block.time >= application.lapses_at {
+        return Err(ApplicationLapsed {});
+    }
+    APPLICATIONS.remove(deps.storage, (&offer_id, &applicant));
+
+    offer.taker = Some(application.applicant.clone());
+    offer.taker_referral = application.referral;
+    offer.taker_recipient = application.recipient;
+    offer.taker_callback = None;
+    OFFERS.save(deps.storage, &offer_id, &offer)?;
+
+    let settlement = settlement_msg(
+        deps.storage,
+        &env,
+        &offer_id,
+        &offer,
+        &application.applicant,
+        vec![application.taker_coin],
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(settlement)
+        .add_attribute("action", "approve_applicant")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("taker", application.applicant))
+}
+
+// Never paused, so escrow can always be returned
+pub fn reject_applicant(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_id: String,
+    applicant: String,
+) -> Result<Response, ContractError> {
+    let offer = OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    if offer.maker != info.sender {
+        return Err(NotOfferMaker {});
+    }
+
+    let applicant = deps.api.addr_validate(&applicant)?;
+    let application = APPLICATIONS
+        .may_load(deps.storage, (&offer_id, &applicant))?
+        .ok_or(NoApplicationFound {})?;
+    APPLICATIONS.remove(deps.storage, (&offer_id, &applicant));
+
+    Ok(Response::new()
+        .add_message(refund_msg(&application)?)
+        .add_attribute("action", "reject_applicant")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("applicant", applicant))
+}
+
+// Anyone may refund lapsed applications since the funds can only go back to their applicants
+pub fn refund_lapsed_applicants(
+    deps: DepsMut,
+    env: Env,
+    offer_id: String,
+) -> Result<Response, ContractError> {
+    let lapsed = APPLICATIONS
+        .prefix(&offer_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|res| {
+            res.as_ref().map_or(true, |(_, application)| {
+                env.block.time >= application.lapses_at
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut response = Response::new().add_attribute("action", "refund_lapsed_applicants");
+    for (applicant, application) in lapsed {
+        APPLICATIONS.remove(deps.storage, (&offer_id, &applicant));
+        response = response
+            .add_message(refund_msg(&application)?)
+            .add_attribute("refunded_applicant", applicant);
+    }
+
+    Ok(response)
+}
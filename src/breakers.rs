@@ -0,0 +1,166 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{BreakerTripped, InvalidVolumeLimit};
+use crate::execute::assert_owner;
+use crate::markets::validate_denom;
+use crate::msg::{BreakerStatus, VolumeLimit};
+use crate::state::{TRIPPED_BREAKERS, VOLUME_LIMITS, WINDOW_VOLUMES};
+use cosmwasm_std::{
+    Coin, DepsMut, Event, MessageInfo, Order, Response, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::Bound;
+
+// Windows are split into this many buckets, so the rolling volume is summed over a fixed number
+// of entries however many offers settle
+const WINDOW_BUCKETS: u64 = 12;
+
+// Index of the bucket `time` falls in, and of the oldest bucket still inside the window
+fn buckets(limit: &VolumeLimit, time: Timestamp) -> (u64, u64) {
+    let bucket_len = (limit.window / WINDOW_BUCKETS).max(1);
+    let current = time.seconds() / bucket_len;
+    (current, (current + 1).saturating_sub(WINDOW_BUCKETS))
+}
+
+// Volume of a denom settled within its rolling window
+pub fn window_volume(
+    storage: &dyn Storage,
+    limit: &VolumeLimit,
+    now: Timestamp,
+) -> StdResult<Uint128> {
+    let (_, oldest) = buckets(limit, now);
+    WINDOW_VOLUMES
+        .prefix(&limit.denom)
+        .range(
+            storage,
+            Some(Bound::inclusive(oldest)),
+            None,
+            Order::Ascending,
+        )
+        .try_fold(Uint128::zero(), |total, res| Ok(total + res?.1))
+}
+
+// Fails if the breaker of any of the given denoms has tripped
+pub fn assert_breakers_closed(storage: &dyn Storage, denoms: &[&str]) -> Result<(), ContractError> {
+    match denoms
+        .iter()
+        .find(|denom| TRIPPED_BREAKERS.has(storage, denom))
+    {
+        Some(denom) => Err(BreakerTripped {
+            denom: denom.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+// Adds settled coins to their denoms' windows, tripping the breaker of every denom whose volume
+// now exceeds its limit. The settlement that exceeds a limit still completes.
+pub fn record_volume(
+    storage: &mut dyn Storage,
+    now: Timestamp,
+    coins: &[&Coin],
+) -> StdResult<Vec<Event>> {
+    let mut events = vec![];
+    for coin in coins {
+        let Some(limit) = VOLUME_LIMITS.may_load(storage, &coin.denom)? else {
+            continue;
+        };
+
+        let (current, oldest) = buckets(&limit, now);
+        let stale = WINDOW_VOLUMES
+            .prefix(&coin.denom)
+            .keys(
+                storage,
+                None,
+                Some(Bound::exclusive(oldest)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        for bucket in stale {
+            WINDOW_VOLUMES.remove(storage, (&coin.denom, bucket));
+        }
+        WINDOW_VOLUMES.update(storage, (&coin.denom, current), |volume| {
+            StdResult::Ok(volume.unwrap_or_default() + coin.amount)
+        })?;
+
+        let volume = window_volume(storage, &limit, now)?;
+        if volume > limit.max_volume && !TRIPPED_BREAKERS.has(storage, &coin.denom) {
+            TRIPPED_BREAKERS.save(storage, &coin.denom, &now)?;
+            events.push(
+                Event::new("circuit_breaker_tripped")
+                    .add_attribute("denom", &coin.denom)
+                    .add_attribute("window_volume", volume)
+                    .add_attribute("max_volume", limit.max_volume),
+            );
+        }
+    }
+    Ok(events)
+}
+
+pub fn update_volume_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    limits: Vec<VolumeLimit>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    for limit in &limits {
+        validate_denom(&limit.denom)?;
+        if limit.max_volume.is_zero() || limit.window == 0 {
+            return Err(InvalidVolumeLimit {
+                denom: limit.denom.clone(),
+            });
+        }
+        // Buckets depend on the window length, so the volume is counted afresh
+        clear_window(deps.storage, &limit.denom)?;
+        VOLUME_LIMITS.save(deps.storage, &limit.denom, limit)?;
+    }
+    for denom in &remove {
+        clear_window(deps.storage, denom)?;
+        VOLUME_LIMITS.remove(deps.storage, denom);
+        TRIPPED_BREAKERS.remove(deps.storage, denom);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_volume_limits")
+        .add_attribute("updated", limits.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+// Owner only. Lets a tripped denom settle again, starting from an empty window.
+pub fn reset_breaker(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    clear_window(deps.storage, &denom)?;
+    TRIPPED_BREAKERS.remove(deps.storage, &denom);
+
+    Ok(Response::new()
+        .add_attribute("action", "reset_breaker")
+        .add_attribute("denom", denom))
+}
+
+fn clear_window(storage: &mut dyn Storage, denom: &str) -> StdResult<()> {
+    let buckets = WINDOW_VOLUMES
+        .prefix(denom)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for bucket in buckets {
+        WINDOW_VOLUMES.remove(storage, (denom, bucket));
+    }
+    Ok(())
+}
+
+pub fn breaker_status(
+    storage: &dyn Storage,
+    limit: VolumeLimit,
+    now: Timestamp,
+) -> StdResult<BreakerStatus> {
+    Ok(BreakerStatus {
+        window_volume: window_volume(storage, &limit, now)?,
+        tripped_at: TRIPPED_BREAKERS.may_load(storage, &limit.denom)?,
+        limit,
+    })
+}
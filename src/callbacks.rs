@@ -0,0 +1,92 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    FlashInProgress, FlashNotRepaid, InaccurateFunds, NoFlashInProgress,
+};
+use crate::msg::{TakerCallback, TakerCallbackMsg};
+use crate::state::{FlashSettlement, FLASH_SETTLEMENT};
+use cosmwasm_std::{
+    to_binary, Coin, DepsMut, MessageInfo, Response, StdResult, Storage, SubMsg, WasmMsg,
+};
+
+pub const FLASH_CALLBACK_REPLY_ID: u64 = 2;
+
+// Delivers the taker's callback once the maker coin has been sent. Flash callbacks are replied to,
+// so the payouts to the maker can wait until the taker has repaid.
+pub fn callback_msg(
+    offer_id: &str,
+    taker: &str,
+    maker_coin: &Coin,
+    taker_coin: &Coin,
+    callback: &TakerCallback,
+) -> StdResult<SubMsg> {
+    let msg = WasmMsg::Execute {
+        contract_addr: taker.to_string(),
+        msg: to_binary(&TakerCallbackMsg::SwapCallback {
+            offer_id: offer_id.to_string(),
+            maker_coin: maker_coin.clone(),
+            taker_coin: taker_coin.clone(),
+            flash: callback.flash,
+            msg: callback.msg.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(match callback.flash {
+        true => SubMsg::reply_on_success(msg, FLASH_CALLBACK_REPLY_ID),
+        false => SubMsg::new(msg),
+    })
+}
+
+// Nothing can settle while a flash taker is yet to repay. The flash take's payouts, including any
+// proceeds action it saved, wait until the callback returns, so settlements made from within it
+// would clobber them.
+pub fn assert_no_flash_in_progress(storage: &dyn Storage) -> Result<(), ContractError> {
+    if FLASH_SETTLEMENT.exists(storage) {
+        return Err(FlashInProgress {});
+    }
+    Ok(())
+}
+
+// Called by the taker from within a flash callback to pay the taker coin
+pub fn repay_flash(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_id: String,
+) -> Result<Response, ContractError> {
+    let mut settlement = FLASH_SETTLEMENT
+        .may_load(deps.storage)?
+        .filter(|settlement| settlement.offer_id == offer_id)
+        .ok_or(NoFlashInProgress {})?;
+    if settlement.repaid || info.funds != [settlement.taker_coin.clone()] {
+        return Err(InaccurateFunds {});
+    }
+
+    settlement.repaid = true;
+    FLASH_SETTLEMENT.save(deps.storage, &settlement)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "repay_flash")
+        .add_attribute("offer_id", offer_id))
+}
+
+// Reverts the whole take unless the taker repaid during the callback, then pays out the maker side
+pub fn handle_flash_callback_reply(deps: DepsMut) -> Result<Response, ContractError> {
+    let FlashSettlement {
+        offer_id,
+        taker_coin,
+        repaid,
+        payouts,
+    } = FLASH_SETTLEMENT.load(deps.storage)?;
+    FLASH_SETTLEMENT.remove(deps.storage);
+
+    if !repaid {
+        return Err(FlashNotRepaid {
+            offer_id,
+            taker_coin,
+        });
+    }
+
+    Ok(Response::new()
+        .add_submessages(payouts)
+        .add_attribute("flash_repaid", offer_id))
+}
@@ -0,0 +1,280 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    CommitmentExists, CommitmentExpired, CommitmentNotEarliest, CommitmentNotMatured,
+    CommitmentsDisabled, InaccurateFunds, InvalidCommitment, InvalidCommitmentConfig,
+    InvalidRevealDelay, NoCommitmentFound, NoOfferFound, TooManyCommitments,
+};
+use crate::execute::{assert_owner, take_offer};
+use crate::migrate::assert_not_migrating;
+use crate::msg::{AllowlistProof, Commitment, CommitmentConfig, Offer, Referral, TakerCallback};
+use crate::state::{
+    COMMITMENTS, COMMITMENT_CONFIG, COMMITMENT_COUNTER, COMMITMENT_SEQUENCE, OFFERS,
+};
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+};
+use cw_storage_plus::Bound;
+use sha2::{Digest, Sha256};
+
+// Longest reveal delay makers can set
+pub const MAX_REVEAL_DELAY: u64 = 40;
+
+// Blocks a commitment can be revealed for once it matures. Pending commitments hold back later
+// ones until then, so the window is kept short.
+pub const REVEAL_WINDOW: u64 = 10;
+
+// Pending commitments an offer takes, which bounds the scan for earlier ones
+pub const MAX_COMMITMENTS: u32 = 20;
+
+pub fn commitment_hash(offer_id: &str, taker: &Addr, salt: &str) -> String {
+    hex::encode(Sha256::digest(
+        format!("{offer_id}:{taker}:{salt}").as_bytes(),
+    ))
+}
+
+// Offers can only require takes by reveal while takers can commit to them
+pub fn validate_reveal_delay(
+    storage: &dyn Storage,
+    reveal_delay: u64,
+) -> Result<(), ContractError> {
+    if reveal_delay == 0 || reveal_delay > MAX_REVEAL_DELAY {
+        return Err(InvalidRevealDelay {
+            reason: format!("must be between 1 and {MAX_REVEAL_DELAY} blocks"),
+        });
+    }
+    if COMMITMENT_CONFIG.may_load(storage)?.is_none() {
+        return Err(CommitmentsDisabled {});
+    }
+    Ok(())
+}
+
+fn is_live(env: &Env, commitment: &Commitment) -> bool {
+    env.block.height < commitment.expires_at_height
+}
+
+// Removes a commitment and pays out its deposit. Lapsed deposits are forfeited to the maker,
+// while live ones are returned to the committer, who either revealed or lost the offer to a close.
+fn remove_commitment(
+    storage: &mut dyn Storage,
+    env: &Env,
+    offer: &Offer,
+    key: &str,
+    commitment: &Commitment,
+) -> StdResult<BankMsg> {
+    COMMITMENTS.remove(storage, key);
+    COMMITMENT_SEQUENCE.remove(storage, (&commitment.offer_id, commitment.sequence));
+
+    let to_address = match is_live(env, commitment) {
+        true => commitment.committer.clone(),
+        false => offer.maker.clone(),
+    };
+    Ok(BankMsg::Send {
+        to_address,
+        amount: vec![Coin::try_from(commitment.deposit.clone())?],
+    })
+}
+
+// Commitments to an offer made before `before`, oldest last
+fn offer_commitments(
+    storage: &dyn Storage,
+    offer_id: &str,
+    before: Option<u64>,
+) -> StdResult<Vec<(String, Commitment)>> {
+    COMMITMENT_SEQUENCE
+        .prefix(offer_id)
+        .range(
+            storage,
+            None,
+            before.map(Bound::exclusive),
+            Order::Descending,
+        )
+        .take(MAX_COMMITMENTS as usize)
+        .map(|item| {
+            let (_, key) = item?;
+            let commitment = COMMITMENTS.load(storage, &key)?;
+            Ok((key, commitment))
+        })
+        .collect()
+}
+
+// Drops the commitments of a closing offer, returning their deposits
+pub fn clear_commitments(
+    storage: &mut dyn Storage,
+    env: &Env,
+    offer_id: &str,
+    offer: &Offer,
+) -> StdResult<Vec<BankMsg>> {
+    offer_commitments(storage, offer_id, None)?
+        .into_iter()
+        .map(|(key, commitment)| remove_commitment(storage, env, offer, &key, &commitment))
+        .collect()
+}
+
+// Earlier live commitments to the same offer take precedence until revealed or expired. Expired
+// ones passed along the way are removed, forfeiting their deposits.
+fn assert_earliest(
+    storage: &mut dyn Storage,
+    env: &Env,
+    offer: &Offer,
+    commitment: &Commitment,
+) -> Result<Vec<BankMsg>, ContractError> {
+    let earlier = offer_commitments(storage, &commitment.offer_id, Some(commitment.sequence))?;
+    let mut forfeits = vec![];
+    for (key, other) in earlier {
+        if !is_live(env, &other) {
+            forfeits.push(remove_commitment(storage, env, offer, &key, &other)?);
+        } else if other.committer != commitment.committer {
+            return Err(CommitmentNotEarliest {
+                expires_at_height: other.expires_at_height,
+            });
+        }
+    }
+    Ok(forfeits)
+}
+
+pub fn commit_take(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+
+    let key = commitment.to_lowercase();
+    if hex::decode(&key).map_or(true, |hash| hash.len() != 32) {
+        return Err(InvalidCommitment {});
+    }
+    let offer = OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    if COMMITMENTS.has(deps.storage, &key) {
+        return Err(CommitmentExists {});
+    }
+    let config = COMMITMENT_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(CommitmentsDisabled {})?;
+    if info.funds != [config.deposit.clone()] {
+        return Err(InaccurateFunds {});
+    }
+
+    // Expired commitments are dropped to make room, and takers hold one commitment per offer
+    let mut response = Response::new();
+    let mut pending = 0;
+    for (other_key, other) in offer_commitments(deps.storage, &offer_id, None)? {
+        if !is_live(&env, &other) {
+            response = response.add_message(remove_commitment(
+                deps.storage,
+                &env,
+                &offer,
+                &other_key,
+                &other,
+            )?);
+        } else if other.committer == info.sender {
+            return Err(CommitmentExists {});
+        } else {
+            pending += 1;
+        }
+    }
+    if pending >= MAX_COMMITMENTS {
+        return Err(TooManyCommitments {
+            max: MAX_COMMITMENTS,
+        });
+    }
+
+    let sequence = COMMITMENT_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    COMMITMENT_COUNTER.save(deps.storage, &sequence)?;
+    // Commitments can only be revealed, and hold back later ones, for a short window once they
+    // mature, so one that's never revealed can't freeze the offer
+    let expires_at_height = env.block.height + offer.reveal_delay.unwrap_or(1) + REVEAL_WINDOW;
+    COMMITMENTS.save(
+        deps.storage,
+        &key,
+        &Commitment {
+            offer_id: offer_id.clone(),
+            committer: info.sender.to_string(),
+            sequence,
+            committed_at: env.block.time,
+            committed_at_height: env.block.height,
+            expires_at_height,
+            deposit: config.deposit.into(),
+        },
+    )?;
+    COMMITMENT_SEQUENCE.save(deps.storage, (&offer_id, sequence), &key)?;
+
+    Ok(response
+        .add_attribute("action", "commit_take")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("committer", info.sender)
+        .add_attribute("sequence", sequence.to_string())
+        .add_attribute("expires_at_height", expires_at_height.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_take(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    salt: String,
+    referral: Option<Referral>,
+    allowlist_proof: Option<AllowlistProof>,
+    recipient: Option<String>,
+    callback: Option<TakerCallback>,
+) -> Result<Response, ContractError> {
+    let key = commitment_hash(&offer_id, &info.sender, &salt);
+    let commitment = COMMITMENTS
+        .may_load(deps.storage, &key)?
+        .ok_or(NoCommitmentFound {})?;
+    if !is_live(&env, &commitment) {
+        return Err(CommitmentExpired {});
+    }
+
+    let offer = OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    // Reveals always land in a later block, so they can't be front-run by a commitment
+    let reveal_at_height = commitment.committed_at_height + offer.reveal_delay.unwrap_or(1);
+    if env.block.height < reveal_at_height {
+        return Err(CommitmentNotMatured { reveal_at_height });
+    }
+    let forfeits = assert_earliest(deps.storage, &env, &offer, &commitment)?;
+    let refund = remove_commitment(deps.storage, &env, &offer, &key, &commitment)?;
+
+    Ok(take_offer(
+        deps,
+        env,
+        info,
+        offer_id,
+        referral,
+        allowlist_proof,
+        recipient,
+        callback,
+    )?
+    .add_messages(forfeits)
+    .add_message(refund)
+    .add_attribute("revealed_commitment", key))
+}
+
+pub fn update_commitment_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<CommitmentConfig>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match config {
+        Some(config) => {
+            if config.deposit.amount.is_zero() {
+                return Err(InvalidCommitmentConfig {});
+            }
+            COMMITMENT_CONFIG.save(deps.storage, &config)?;
+        }
+        None => COMMITMENT_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_commitment_config"))
+}
@@ -1,19 +1,112 @@
+use crate::actions::{
+    handle_proceeds_action_reply, update_action_contracts, PROCEEDS_ACTION_REPLY_ID,
+};
+use crate::approvals::{approve_applicant, refund_lapsed_applicants, reject_applicant};
+use crate::breakers::{reset_breaker, update_volume_limits};
+use crate::callbacks::{handle_flash_callback_reply, repay_flash, FLASH_CALLBACK_REPLY_ID};
+use crate::commitments::{commit_take, reveal_take, update_commitment_config};
+use crate::contract_offers::{
+    cancel_contract_offer, handle_confirm_fill_reply, make_contract_offer, take_contract_offer,
+    CONFIRM_FILL_REPLY_ID,
+};
+use crate::counters::{accept_counter, counter, reject_counter, withdraw_counter};
+use crate::denoms::update_ibc_channels;
 use crate::error::ContractError;
-use crate::execute::{fulfill_offer, make_offer, provide_taker};
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::query::{query_all_offers, query_fulfilled_offers};
+use crate::execute::{
+    accept_ownership, bond, cancel_offer, claim_rewards, fulfill_offer, make_offer,
+    propose_new_owner, provide_ibc_taker, provide_taker, receive_cw20, renounce_ownership, unbond,
+    update_config, update_fees, update_guardian,
+};
+use crate::fees::{save_pair_fees, validate_fee_bps};
+use crate::gates::{update_access_gate, validate_access_gate};
+use crate::governance::{
+    delist_denom, force_cancel_offer, governance_set_pause, relist_denom, replace_owner,
+};
+use crate::ibc::{
+    handle_ibc_transfer_reply, ibc_lifecycle_complete, retry_ibc_transfer, IBC_TRANSFER_REPLY_ID,
+};
+use crate::limits::{prune_expired_offers, update_maker_limits};
+use crate::maker_bonds::{handle_maker_pull_reply, update_maker_bond_config, MAKER_PULL_REPLY_ID};
+use crate::markets::{update_denoms, update_markets};
+use crate::migrate::{continue_migration, migrate_contract};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+use crate::pause::set_pause;
+use crate::query::{
+    query_action_contracts, query_all_offers, query_allowlist_taken, query_applicants,
+    query_audit_log, query_breakers, query_commitment, query_commitment_config, query_config,
+    query_contract_info, query_contract_offer, query_contract_offer_quote, query_counters_by_offer,
+    query_counters_by_proposer, query_delisted_denoms, query_fees, query_fulfilled_offers,
+    query_ibc_channels, query_ibc_transfer, query_maker_bond_config, query_maker_bonds,
+    query_maker_limits, query_maker_open_offers, query_markets, query_migration_progress,
+    query_offers_with_denoms, query_pauses, query_pending_rewards, query_referrer_stats,
+    query_reservation_config, query_staker, query_staking_state,
+};
+use crate::reservations::{reserve_offer, update_reservation_config};
+use crate::staking::validate_staking_token;
+use crate::state::{Config, FeeConfig, CONFIG, STAKING_TOKEN, TOTAL_BONDED};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, Uint128,
 };
+use cw2::set_contract_version;
+
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
-    _msg: InstantiateMsg,
-) -> StdResult<Response> {
-    Ok(Response::default())
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    validate_fee_bps(msg.fee_bps)?;
+    validate_fee_bps(msg.max_referral_fee_bps)?;
+    if msg.max_query_limit == 0 {
+        return Err(ContractError::InvalidQueryLimit {});
+    }
+
+    if let Some(gate) = &msg.access_gate {
+        validate_access_gate(deps.api, gate)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: Some(owner.clone()),
+            guardian: msg
+                .guardian
+                .map(|guardian| deps.api.addr_validate(&guardian))
+                .transpose()?,
+            fees: FeeConfig {
+                fee_bps: msg.fee_bps,
+                fee_recipient: deps.api.addr_validate(&msg.fee_recipient)?,
+                fee_payer: msg.fee_payer,
+                max_referral_fee_bps: msg.max_referral_fee_bps,
+                referral_fee_source: msg.referral_fee_source,
+            },
+            max_query_limit: msg.max_query_limit,
+            access_gate: msg.access_gate,
+        },
+    )?;
+    save_pair_fees(deps.storage, &msg.pair_fees)?;
+
+    if let Some(staking_token) = msg.staking_token {
+        let staking_token = validate_staking_token(deps.as_ref(), staking_token)?;
+        STAKING_TOKEN.save(deps.storage, &staking_token)?;
+        TOTAL_BONDED.save(deps.storage, &Uint128::zero())?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("owner", owner)
+        .add_attribute("fee_bps", msg.fee_bps.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -27,21 +120,288 @@ pub fn execute(
         ExecuteMsg::MakeOffer {
             maker_coin,
             taker_coin,
-        } => make_offer(deps, env, info, maker_coin, taker_coin),
-        ExecuteMsg::OfferTaker { offer_id } => provide_taker(deps, env, info, offer_id),
+            referral,
+            access_gate,
+            taker_allowlist,
+            holding_requirements,
+            proceeds_recipient,
+            payout_split,
+            proceeds_action,
+            approval_window,
+            reveal_delay,
+            bond,
+        } => make_offer(
+            deps,
+            env,
+            info,
+            maker_coin,
+            taker_coin,
+            referral,
+            access_gate,
+            taker_allowlist,
+            holding_requirements,
+            proceeds_recipient,
+            payout_split,
+            proceeds_action,
+            approval_window,
+            reveal_delay,
+            bond,
+        ),
+        ExecuteMsg::OfferTaker {
+            offer_id,
+            referral,
+            allowlist_proof,
+            recipient,
+            callback,
+        } => provide_taker(
+            deps,
+            env,
+            info,
+            offer_id,
+            referral,
+            allowlist_proof,
+            recipient,
+            callback,
+        ),
+        ExecuteMsg::CommitTake {
+            offer_id,
+            commitment,
+        } => commit_take(deps, env, info, offer_id, commitment),
+        ExecuteMsg::RevealTake {
+            offer_id,
+            salt,
+            referral,
+            allowlist_proof,
+            recipient,
+            callback,
+        } => reveal_take(
+            deps,
+            env,
+            info,
+            offer_id,
+            salt,
+            referral,
+            allowlist_proof,
+            recipient,
+            callback,
+        ),
+        ExecuteMsg::ReserveOffer { offer_id, blocks } => {
+            reserve_offer(deps, env, info, offer_id, blocks)
+        }
+        ExecuteMsg::RepayFlash { offer_id } => repay_flash(deps, info, offer_id),
+        ExecuteMsg::IbcOfferTaker {
+            offer_id,
+            channel,
+            receiver,
+            recovery_addr,
+        } => provide_ibc_taker(deps, env, info, offer_id, channel, receiver, recovery_addr),
+        ExecuteMsg::RetryIbcTransfer { offer_id } => retry_ibc_transfer(deps, env, offer_id),
         ExecuteMsg::FulfillOffer { offer_id } => fulfill_offer(deps, env, info, offer_id),
+        ExecuteMsg::CancelOffer { offer_id } => cancel_offer(deps, env, info, offer_id),
+        ExecuteMsg::ApproveApplicant {
+            offer_id,
+            applicant,
+        } => approve_applicant(deps, env, info, offer_id, applicant),
+        ExecuteMsg::RejectApplicant {
+            offer_id,
+            applicant,
+        } => reject_applicant(deps, info, offer_id, applicant),
+        ExecuteMsg::RefundLapsedApplicants { offer_id } => {
+            refund_lapsed_applicants(deps, env, offer_id)
+        }
+        ExecuteMsg::Counter {
+            offer_id,
+            taker_coin,
+        } => counter(deps, env, info, offer_id, taker_coin),
+        ExecuteMsg::AcceptCounter { counter_id } => accept_counter(deps, env, info, counter_id),
+        ExecuteMsg::RejectCounter { counter_id } => reject_counter(deps, info, counter_id),
+        ExecuteMsg::WithdrawCounter { counter_id } => withdraw_counter(deps, info, counter_id),
+        ExecuteMsg::MakeContractOffer {
+            maker_asset,
+            taker_denom,
+            settlement,
+        } => make_contract_offer(deps, env, info, maker_asset, taker_denom, settlement),
+        ExecuteMsg::TakeContractOffer {
+            offer_id,
+            min_maker_amount,
+            referral,
+        } => take_contract_offer(deps, env, info, offer_id, min_maker_amount, referral),
+        ExecuteMsg::CancelContractOffer { offer_id } => cancel_contract_offer(deps, info, offer_id),
+        ExecuteMsg::UpdateFees {
+            fee_bps,
+            fee_recipient,
+            fee_payer,
+            pair_fees,
+            max_referral_fee_bps,
+            referral_fee_source,
+        } => update_fees(
+            deps,
+            info,
+            fee_bps,
+            fee_recipient,
+            fee_payer,
+            pair_fees,
+            max_referral_fee_bps,
+            referral_fee_source,
+        ),
+        ExecuteMsg::Bond {} => bond(deps, info),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::Unbond { amount } => unbond(deps, info, amount),
+        ExecuteMsg::ClaimRewards {} => claim_rewards(deps, info),
+        ExecuteMsg::UpdateConfig { max_query_limit } => update_config(deps, info, max_query_limit),
+        ExecuteMsg::ProposeNewOwner { owner } => propose_new_owner(deps, info, owner),
+        ExecuteMsg::AcceptOwnership {} => accept_ownership(deps, info),
+        ExecuteMsg::RenounceOwnership {} => renounce_ownership(deps, info),
+        ExecuteMsg::UpdateDenoms {
+            allow,
+            block,
+            unlist,
+        } => update_denoms(deps, info, allow, block, unlist),
+        ExecuteMsg::UpdateMarkets {
+            restricted,
+            markets,
+            remove,
+        } => update_markets(deps, info, restricted, markets, remove),
+        ExecuteMsg::UpdateIbcChannels {
+            require_approved,
+            approve,
+            revoke,
+        } => update_ibc_channels(deps, info, require_approved, approve, revoke),
+        ExecuteMsg::UpdateActionContracts { approve, revoke } => {
+            update_action_contracts(deps, info, approve, revoke)
+        }
+        ExecuteMsg::UpdateReservationConfig { config } => {
+            update_reservation_config(deps, info, config)
+        }
+        ExecuteMsg::UpdateCommitmentConfig { config } => {
+            update_commitment_config(deps, info, config)
+        }
+        ExecuteMsg::UpdateMakerBondConfig { config } => {
+            update_maker_bond_config(deps, info, config)
+        }
+        ExecuteMsg::UpdateMakerLimits { limits } => update_maker_limits(deps, info, limits),
+        ExecuteMsg::UpdateVolumeLimits { limits, remove } => {
+            update_volume_limits(deps, info, limits, remove)
+        }
+        ExecuteMsg::ResetBreaker { denom } => reset_breaker(deps, info, denom),
+        ExecuteMsg::PruneExpiredOffers { limit } => prune_expired_offers(deps, env, limit),
+        ExecuteMsg::UpdateAccessGate { access_gate } => update_access_gate(deps, info, access_gate),
+        ExecuteMsg::UpdateGuardian { guardian } => update_guardian(deps, info, guardian),
+        ExecuteMsg::SetPause {
+            operation,
+            denom,
+            paused,
+        } => set_pause(deps, info, operation, denom, paused),
+        ExecuteMsg::ContinueMigration { limit } => continue_migration(deps, info, limit),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, StdError> {
-    let res = match msg {
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate_contract(deps, env, msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::IBCLifecycleComplete(lifecycle) => ibc_lifecycle_complete(deps, env, lifecycle),
+        SudoMsg::ForceCancelOffer { offer_id } => force_cancel_offer(deps, env, offer_id),
+        SudoMsg::DelistDenom { denom } => delist_denom(deps, env, denom),
+        SudoMsg::RelistDenom { denom } => relist_denom(deps, env, denom),
+        SudoMsg::SetPause {
+            operation,
+            denom,
+            paused,
+        } => governance_set_pause(deps, env, operation, denom, paused),
+        SudoMsg::ReplaceOwner { owner } => replace_owner(deps, env, owner),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        IBC_TRANSFER_REPLY_ID => handle_ibc_transfer_reply(deps, msg),
+        FLASH_CALLBACK_REPLY_ID => handle_flash_callback_reply(deps),
+        PROCEEDS_ACTION_REPLY_ID => handle_proceeds_action_reply(deps, msg),
+        CONFIRM_FILL_REPLY_ID => handle_confirm_fill_reply(deps),
+        MAKER_PULL_REPLY_ID => handle_maker_pull_reply(deps, env, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, StdError> {
+    match msg {
         QueryMsg::AllOffers { start_after, limit } => {
             to_binary(&query_all_offers(deps, start_after, limit)?)
         }
         QueryMsg::FulfilledOffers { offer_id } => {
             to_binary(&query_fulfilled_offers(deps, offer_id)?)
         }
-    };
-    res.map_err(Into::into)
+        QueryMsg::IbcTransfer { offer_id } => to_binary(&query_ibc_transfer(deps, offer_id)?),
+        QueryMsg::Fees {} => to_binary(&query_fees(deps)?),
+        QueryMsg::StakingState {} => to_binary(&query_staking_state(deps)?),
+        QueryMsg::Staker { address } => to_binary(&query_staker(deps, address)?),
+        QueryMsg::PendingRewards { staker } => to_binary(&query_pending_rewards(deps, staker)?),
+        QueryMsg::ReferrerStats { referrer } => to_binary(&query_referrer_stats(deps, referrer)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::ContractInfo {} => to_binary(&query_contract_info(deps)?),
+        QueryMsg::MigrationProgress {} => to_binary(&query_migration_progress(deps)?),
+        QueryMsg::Pauses {} => to_binary(&query_pauses(deps)?),
+        QueryMsg::DelistedDenoms {} => to_binary(&query_delisted_denoms(deps)?),
+        QueryMsg::Markets {} => to_binary(&query_markets(deps)?),
+        QueryMsg::OffersWithDenoms { start_after, limit } => {
+            to_binary(&query_offers_with_denoms(deps, start_after, limit)?)
+        }
+        QueryMsg::IbcChannels {} => to_binary(&query_ibc_channels(deps)?),
+        QueryMsg::Applicants { offer_id } => to_binary(&query_applicants(deps, offer_id)?),
+        QueryMsg::CountersByOffer {
+            offer_id,
+            start_after,
+            limit,
+        } => to_binary(&query_counters_by_offer(
+            deps,
+            offer_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::CountersByProposer {
+            proposer,
+            start_after,
+            limit,
+        } => to_binary(&query_counters_by_proposer(
+            deps,
+            proposer,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ContractOffer { offer_id } => to_binary(&query_contract_offer(deps, offer_id)?),
+        QueryMsg::ContractOfferQuote {
+            offer_id,
+            taker,
+            taker_coin,
+        } => to_binary(&query_contract_offer_quote(
+            deps, offer_id, taker, taker_coin,
+        )?),
+        QueryMsg::ActionContracts {} => to_binary(&query_action_contracts(deps)?),
+        QueryMsg::Breakers {} => to_binary(&query_breakers(deps, env)?),
+        QueryMsg::MakerLimits {} => to_binary(&query_maker_limits(deps)?),
+        QueryMsg::Commitment { commitment } => to_binary(&query_commitment(deps, commitment)?),
+        QueryMsg::CommitmentConfig {} => to_binary(&query_commitment_config(deps)?),
+        QueryMsg::MakerBondConfig {} => to_binary(&query_maker_bond_config(deps)?),
+        QueryMsg::MakerBonds {
+            maker,
+            start_after,
+            limit,
+        } => to_binary(&query_maker_bonds(deps, maker, start_after, limit)?),
+        QueryMsg::ReservationConfig {} => to_binary(&query_reservation_config(deps)?),
+        QueryMsg::MakerOpenOffers { maker } => to_binary(&query_maker_open_offers(deps, maker)?),
+        QueryMsg::AllowlistTaken {
+            merkle_root,
+            address,
+        } => to_binary(&query_allowlist_taken(deps, merkle_root, address)?),
+        QueryMsg::AuditLog { start_after, limit } => {
+            to_binary(&query_audit_log(deps, start_after, limit)?)
+        }
+    }
 }
@@ -0,0 +1,373 @@
+use crate::breakers::{assert_breakers_closed, record_volume};
+use crate::callbacks::assert_no_flash_in_progress;
+use crate::denoms::assert_approved_ibc_denoms;
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    FillNotDelivered, InaccurateFunds, InvalidContractOffer, NoOfferFound, NotMakerContract,
+    NotOfferMaker, QuoteSizeExceeded, SlippageExceeded,
+};
+use crate::execute::assert_not_module_account;
+use crate::fees::{
+    calculate_settlement_fees, fee_bps_for_pair, record_referral_fee, validate_referral,
+};
+use crate::gates::assert_authorized;
+use crate::governance::assert_listed;
+use crate::markets::{validate_market, validate_market_denoms};
+use crate::migrate::assert_not_migrating;
+use crate::msg::{
+    ContractOffer, FeePayer, FillSettlement, MakerAsset, MakerExecuteMsg, MakerQueryMsg, Operation,
+    Quote, Referral,
+};
+use crate::pause::assert_not_paused;
+use crate::staking::distribute_fee;
+use crate::state::{PendingFill, CONFIG, CONTRACT_OFFERS, OFFER_ID_COUNTER, PENDING_FILL};
+use cosmwasm_std::{
+    coin, to_binary, Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, QuerierWrapper, Response,
+    StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+
+pub const CONFIRM_FILL_REPLY_ID: u64 = 4;
+
+fn asset_balance(
+    querier: &QuerierWrapper,
+    asset: &MakerAsset,
+    address: &str,
+) -> StdResult<Uint128> {
+    match asset {
+        MakerAsset::Native { denom } => Ok(querier.query_balance(address, denom)?.amount),
+        MakerAsset::Cw20 { contract } => {
+            let res: BalanceResponse = querier.query_wasm_smart(
+                contract,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            Ok(res.balance)
+        }
+    }
+}
+
+// Asks the maker contract for its quote, returning the maker amount for the taker coin and the
+// most a single fill can pay out
+pub fn quote(
+    querier: &QuerierWrapper,
+    offer_id: &str,
+    offer: &ContractOffer,
+    taker: &str,
+    taker_coin: &Coin,
+) -> StdResult<(Uint128, Uint128)> {
+    let quote: Quote = querier.query_wasm_smart(
+        &offer.maker,
+        &MakerQueryMsg::QuoteRequest {
+            offer_id: offer_id.to_string(),
+            taker: taker.to_string(),
+            taker_coin: taker_coin.clone(),
+        },
+    )?;
+    let maker_amount = taker_coin
+        .amount
+        .checked_mul_floor(quote.price)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let max_maker_amount = match offer.settlement {
+        FillSettlement::Escrow {} => quote.max_maker_amount.min(offer.escrow),
+        FillSettlement::MakerTransfer {} => quote.max_maker_amount,
+    };
+    Ok((maker_amount, max_maker_amount))
+}
+
+pub fn make_contract_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    maker_asset: MakerAsset,
+    taker_denom: String,
+    settlement: FillSettlement,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    assert_not_paused(
+        deps.storage,
+        Operation::Make,
+        &[maker_asset.denom(), &taker_denom],
+    )?;
+    assert_listed(deps.storage, &[maker_asset.denom(), &taker_denom])?;
+
+    // Quotes are queried from the maker, so it has to be a contract
+    deps.querier
+        .query_wasm_contract_info(&info.sender)
+        .map_err(|_| NotMakerContract {})?;
+
+    if let MakerAsset::Cw20 { contract } = &maker_asset {
+        deps.api.addr_validate(contract)?;
+    }
+    validate_market_denoms(deps.storage, maker_asset.denom(), &taker_denom)?;
+    assert_approved_ibc_denoms(
+        deps.storage,
+        &deps.querier,
+        &[maker_asset.denom(), &taker_denom],
+    )?;
+    if let Some(gate) = &CONFIG.load(deps.storage)?.access_gate {
+        assert_authorized(&deps.querier, gate, &info.sender, Operation::Make)?;
+    }
+
+    let escrow = match (&settlement, &maker_asset) {
+        (FillSettlement::Escrow {}, MakerAsset::Native { denom }) => match info.funds.as_slice() {
+            [escrow] if &escrow.denom == denom && !escrow.amount.is_zero() => escrow.amount,
+            _ => return Err(InaccurateFunds {}),
+        },
+        (FillSettlement::Escrow {}, MakerAsset::Cw20 { .. }) => {
+            return Err(InvalidContractOffer {
+                reason: "only native coins can be escrowed".to_string(),
+            })
+        }
+        (FillSettlement::MakerTransfer {}, _) if !info.funds.is_empty() => {
+            return Err(InaccurateFunds {})
+        }
+        (FillSettlement::MakerTransfer {}, _) => Uint128::zero(),
+    };
+
+    // Contract offers share ids with regular offers
+    let offer_id = OFFER_ID_COUNTER.may_load(deps.storage)?.unwrap_or_default() + 1;
+    OFFER_ID_COUNTER.save(deps.storage, &offer_id)?;
+    CONTRACT_OFFERS.save(
+        deps.storage,
+        &offer_id.to_string(),
+        &ContractOffer {
+            maker: info.sender.to_string(),
+            maker_asset: maker_asset.clone(),
+            taker_denom: taker_denom.clone(),
+            settlement,
+            escrow,
+            created_at: env.block.time,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "make_contract_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("maker_asset", maker_asset.denom())
+        .add_attribute("taker_denom", taker_denom)
+        .add_attribute("escrow", escrow))
+}
+
+pub fn take_contract_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    min_maker_amount: Uint128,
+    referral: Option<Referral>,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    assert_no_flash_in_progress(deps.storage)?;
+
+    let mut offer = CONTRACT_OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    let denoms = [offer.maker_asset.denom(), offer.taker_denom.as_str()];
+    assert_not_paused(deps.storage, Operation::Take, &denoms)?;
+    assert_not_paused(deps.storage, Operation::Settle, &denoms)?;
+    assert_listed(deps.storage, &denoms)?;
+    assert_breakers_closed(deps.storage, &denoms)?;
+    let config = CONFIG.load(deps.storage)?;
+    if let Some(gate) = &config.access_gate {
+        assert_authorized(&deps.querier, gate, &info.sender, Operation::Take)?;
+    }
+
+    // Taker side fees come out of the maker coin, which only escrowed offers hand to the contract
+    if config.fees.fee_payer == FeePayer::Taker
+        && offer.settlement == (FillSettlement::MakerTransfer {})
+    {
+        return Err(InvalidContractOffer {
+            reason: "maker transfer offers can't be taken while takers pay fees".to_string(),
+        });
+    }
+    let referral = validate_referral(deps.api, &config.fees, referral)?;
+    if let Some(referral) = &referral {
+        assert_not_module_account(&deps.querier, &referral.address)?;
+    }
+
+    let taker_coin = match info.funds.as_slice() {
+        [taker_coin] if taker_coin.denom == offer.taker_denom && !taker_coin.amount.is_zero() => {
+            taker_coin.clone()
+        }
+        _ => return Err(InaccurateFunds {}),
+    };
+
+    let (maker_amount, max_maker_amount) = quote(
+        &deps.querier,
+        &offer_id,
+        &offer,
+        info.sender.as_str(),
+        &taker_coin,
+    )?;
+    if maker_amount > max_maker_amount {
+        return Err(QuoteSizeExceeded { max_maker_amount });
+    }
+    let maker_coin = coin(maker_amount.u128(), offer.maker_asset.denom());
+
+    // The protocol fee and referral fee are deducted from the side configured to pay them, as
+    // with regular offers
+    let fee_bps = fee_bps_for_pair(deps.storage, offer.maker_asset.denom(), &offer.taker_denom)?;
+    let fee_base = match config.fees.fee_payer {
+        FeePayer::Maker => &taker_coin,
+        FeePayer::Taker => &maker_coin,
+    };
+    let fees = calculate_settlement_fees(
+        fee_base,
+        fee_bps,
+        &referral.into_iter().collect::<Vec<_>>(),
+        &config.fees.referral_fee_source,
+    );
+    let (maker_coin_to_taker, proceeds) = match config.fees.fee_payer {
+        FeePayer::Maker => (
+            maker_coin.clone(),
+            coin((taker_coin.amount - fees.total).u128(), &taker_coin.denom),
+        ),
+        FeePayer::Taker => (
+            coin((maker_amount - fees.total).u128(), &maker_coin.denom),
+            taker_coin.clone(),
+        ),
+    };
+
+    // Slippage is checked on what the taker receives
+    if maker_coin_to_taker.amount.is_zero() || maker_coin_to_taker.amount < min_maker_amount {
+        return Err(SlippageExceeded {
+            maker_amount: maker_coin_to_taker.amount,
+            min_maker_amount,
+        });
+    }
+
+    // Markets are checked again with the quoted amounts, as regular offers are when made
+    validate_market(deps.storage, &maker_coin, &taker_coin)?;
+
+    let mut settled = vec![&taker_coin];
+    if let MakerAsset::Native { .. } = offer.maker_asset {
+        settled.push(&maker_coin);
+    }
+    let breaker_events = record_volume(deps.storage, env.block.time, &settled)?;
+
+    // Fees are paid from the contract, which holds the taker coin and any escrowed maker coin
+    let fee = fees.protocol_fee;
+    let fee_to_stakers = distribute_fee(deps.storage, &fee)?;
+    let mut response = Response::new().add_events(breaker_events);
+    if !fee.amount.is_zero() && !fee_to_stakers {
+        response = response.add_message(BankMsg::Send {
+            to_address: config.fees.fee_recipient.to_string(),
+            amount: vec![fee.clone()],
+        });
+    }
+    for (referral, referral_fee) in &fees.referral_fees {
+        record_referral_fee(
+            deps.storage,
+            &Addr::unchecked(&referral.address),
+            referral_fee,
+        )?;
+        if !referral_fee.amount.is_zero() {
+            response = response.add_message(BankMsg::Send {
+                to_address: referral.address.clone(),
+                amount: vec![referral_fee.clone()],
+            });
+        }
+        response = response
+            .add_attribute("referrer", &referral.address)
+            .add_attribute("referral_fee", referral_fee.to_string());
+    }
+
+    let confirm_fill = WasmMsg::Execute {
+        contract_addr: offer.maker.clone(),
+        msg: to_binary(&MakerExecuteMsg::ConfirmFill {
+            offer_id: offer_id.clone(),
+            taker: info.sender.to_string(),
+            maker_amount,
+            taker_coin: proceeds.clone(),
+        })?,
+        funds: vec![proceeds],
+    };
+    match offer.settlement {
+        FillSettlement::Escrow {} => {
+            offer.escrow -= maker_amount;
+            CONTRACT_OFFERS.save(deps.storage, &offer_id, &offer)?;
+            response = response
+                .add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: vec![maker_coin_to_taker.clone()],
+                })
+                .add_message(confirm_fill);
+        }
+        FillSettlement::MakerTransfer {} => {
+            // The maker pays the taker itself, so the taker's balance is checked once it's done
+            let balance = asset_balance(&deps.querier, &offer.maker_asset, info.sender.as_str())?;
+            PENDING_FILL.save(
+                deps.storage,
+                &PendingFill {
+                    offer_id: offer_id.clone(),
+                    taker: info.sender.to_string(),
+                    maker_asset: offer.maker_asset.clone(),
+                    maker_amount,
+                    expected_balance: balance + maker_amount,
+                },
+            )?;
+            response = response.add_submessage(SubMsg::reply_on_success(
+                confirm_fill,
+                CONFIRM_FILL_REPLY_ID,
+            ));
+        }
+    }
+
+    Ok(response
+        .add_attribute("action", "take_contract_offer")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("maker_amount", maker_amount)
+        .add_attribute("maker_coin_to_taker", maker_coin_to_taker.to_string())
+        .add_attribute("taker_coin", taker_coin.to_string())
+        .add_attribute("fee", fee.to_string())
+        .add_attribute(
+            "fee_payer",
+            format!("{:?}", config.fees.fee_payer).to_lowercase(),
+        )
+        .add_attribute("fee_to_stakers", fee_to_stakers.to_string()))
+}
+
+pub fn handle_confirm_fill_reply(deps: DepsMut) -> Result<Response, ContractError> {
+    let fill = PENDING_FILL.load(deps.storage)?;
+    PENDING_FILL.remove(deps.storage);
+
+    let balance = asset_balance(&deps.querier, &fill.maker_asset, &fill.taker)?;
+    if balance < fill.expected_balance {
+        return Err(FillNotDelivered {
+            offer_id: fill.offer_id,
+            maker_amount: fill.maker_amount,
+        });
+    }
+
+    Ok(Response::new().add_attribute("fill_delivered", fill.offer_id))
+}
+
+// Never paused, so makers can always withdraw their escrow
+pub fn cancel_contract_offer(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_id: String,
+) -> Result<Response, ContractError> {
+    let offer = CONTRACT_OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    if offer.maker != info.sender {
+        return Err(NotOfferMaker {});
+    }
+    CONTRACT_OFFERS.remove(deps.storage, &offer_id);
+
+    let mut response = Response::new();
+    if !offer.escrow.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: offer.maker,
+            amount: vec![coin(offer.escrow.u128(), offer.maker_asset.denom())],
+        });
+    }
+
+    Ok(response
+        .add_attribute("action", "cancel_contract_offer")
+        .add_attribute("offer_id", offer_id))
+}
@@ -0,0 +1,216 @@
+use crate::callbacks::assert_no_flash_in_progress;
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    CounterExpired, InaccurateFunds, InvalidCounter, NoCounterFound, NoOfferFound,
+    NotCounterProposer, NotOfferMaker, OfferExpired,
+};
+use crate::gates::assert_authorized;
+use crate::governance::assert_listed;
+use crate::holdings::assert_holdings;
+use crate::maker_bonds::settlement_msg;
+use crate::markets::validate_market;
+use crate::migrate::assert_not_migrating;
+use crate::msg::{Counter, Offer, Operation};
+use crate::pause::assert_not_paused;
+use crate::state::{
+    CONFIG, COUNTERS, COUNTER_ID_COUNTER, OFFERS, OFFER_COUNTERS, PROPOSER_COUNTERS,
+};
+use cosmwasm_std::{Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Storage};
+use osmosis_std::types::cosmos::base::v1beta1::Coin as Coin2;
+
+// Seconds a counter can be accepted for
+pub const COUNTER_TTL: u64 = 24 * 60 * 60;
+
+// Offers can only settle until they expire, even before they are pruned
+pub fn load_open_offer(
+    storage: &dyn Storage,
+    env: &Env,
+    offer_id: &str,
+) -> Result<Offer, ContractError> {
+    let offer = OFFERS.may_load(storage, offer_id)?.ok_or(NoOfferFound {})?;
+    if offer
+        .expires_at
+        .is_some_and(|expires_at| env.block.time >= expires_at)
+    {
+        return Err(OfferExpired {});
+    }
+    Ok(offer)
+}
+
+fn remove_counter(storage: &mut dyn Storage, counter: &Counter) {
+    COUNTERS.remove(storage, counter.counter_id);
+    OFFER_COUNTERS.remove(storage, (&counter.offer_id, counter.counter_id));
+    PROPOSER_COUNTERS.remove(
+        storage,
+        (&Addr::unchecked(&counter.proposer), counter.counter_id),
+    );
+}
+
+fn refund_msg(counter: &Counter) -> Result<BankMsg, ContractError> {
+    Ok(BankMsg::Send {
+        to_address: counter.proposer.clone(),
+        amount: vec![Coin::try_from(counter.taker_coin.clone())?],
+    })
+}
+
+pub fn counter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    taker_coin: Coin2,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+
+    let offer = load_open_offer(deps.storage, &env, &offer_id)?;
+    let escrow = Coin::try_from(taker_coin.clone())?;
+    assert_not_paused(
+        deps.storage,
+        Operation::Take,
+        &[&offer.maker_coin.denom, &escrow.denom],
+    )?;
+    assert_listed(deps.storage, &[&offer.maker_coin.denom, &escrow.denom])?;
+    validate_market(
+        deps.storage,
+        &Coin::try_from(offer.maker_coin.clone())?,
+        &escrow,
+    )?;
+
+    // Proposers have to be able to take the offer, except that allowlist caps are only tracked
+    // for direct takes
+    if offer.taker_allowlist.is_some() {
+        return Err(InvalidCounter {
+            reason: "offers with a taker allowlist can't be countered".to_string(),
+        });
+    }
+    let gates = [
+        CONFIG.load(deps.storage)?.access_gate,
+        offer.access_gate.clone(),
+    ];
+    for gate in gates.iter().flatten() {
+        assert_authorized(&deps.querier, gate, &info.sender, Operation::Take)?;
+    }
+    if let Some(requirements) = &offer.holding_requirements {
+        assert_holdings(&deps.querier, requirements, &info.sender)?;
+    }
+
+    if info.funds.len() != 1 || info.funds[0] != escrow {
+        return Err(InaccurateFunds {});
+    }
+
+    let counter_id = COUNTER_ID_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    COUNTER_ID_COUNTER.save(deps.storage, &counter_id)?;
+    COUNTERS.save(
+        deps.storage,
+        counter_id,
+        &Counter {
+            counter_id,
+            offer_id: offer_id.clone(),
+            proposer: info.sender.to_string(),
+            taker_coin,
+            created_at: env.block.time,
+            expires_at: env.block.time.plus_seconds(COUNTER_TTL),
+        },
+    )?;
+    OFFER_COUNTERS.save(deps.storage, (&offer_id, counter_id), &true)?;
+    PROPOSER_COUNTERS.save(deps.storage, (&info.sender, counter_id), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "counter")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("counter_id", counter_id.to_string())
+        .add_attribute("proposer", info.sender)
+        .add_attribute("taker_coin", escrow.to_string()))
+}
+
+// Settles the offer through the regular fulfilment with the counter's proposer as the taker
+pub fn accept_counter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    counter_id: u64,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    assert_no_flash_in_progress(deps.storage)?;
+
+    let counter = COUNTERS
+        .may_load(deps.storage, counter_id)?
+        .ok_or(NoCounterFound {})?;
+    let mut offer = load_open_offer(deps.storage, &env, &counter.offer_id)?;
+    if offer.maker != info.sender {
+        return Err(NotOfferMaker {});
+    }
+    if env.block.time >= counter.expires_at {
+        return Err(CounterExpired {});
+    }
+    remove_counter(deps.storage, &counter);
+
+    offer.taker = Some(counter.proposer.clone());
+    offer.taker_coin = counter.taker_coin.clone();
+    offer.taker_referral = None;
+    offer.taker_recipient = None;
+    offer.taker_callback = None;
+    OFFERS.save(deps.storage, &counter.offer_id, &offer)?;
+
+    let settlement = settlement_msg(
+        deps.storage,
+        &env,
+        &counter.offer_id,
+        &offer,
+        &counter.proposer,
+        vec![counter.taker_coin],
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(settlement)
+        .add_attribute("action", "accept_counter")
+        .add_attribute("offer_id", counter.offer_id)
+        .add_attribute("counter_id", counter_id.to_string())
+        .add_attribute("taker", counter.proposer))
+}
+
+// Never paused, so escrow can always be returned
+pub fn reject_counter(
+    deps: DepsMut,
+    info: MessageInfo,
+    counter_id: u64,
+) -> Result<Response, ContractError> {
+    let counter = COUNTERS
+        .may_load(deps.storage, counter_id)?
+        .ok_or(NoCounterFound {})?;
+    let offer = OFFERS
+        .may_load(deps.storage, &counter.offer_id)?
+        .ok_or(NoOfferFound {})?;
+    if offer.maker != info.sender {
+        return Err(NotOfferMaker {});
+    }
+    remove_counter(deps.storage, &counter);
+
+    Ok(Response::new()
+        .add_message(refund_msg(&counter)?)
+        .add_attribute("action", "reject_counter")
+        .add_attribute("counter_id", counter_id.to_string()))
+}
+
+// Allowed whatever happened to the offer, so escrow can't get stuck once it closes
+pub fn withdraw_counter(
+    deps: DepsMut,
+    info: MessageInfo,
+    counter_id: u64,
+) -> Result<Response, ContractError> {
+    let counter = COUNTERS
+        .may_load(deps.storage, counter_id)?
+        .ok_or(NoCounterFound {})?;
+    if counter.proposer != info.sender {
+        return Err(NotCounterProposer {});
+    }
+    remove_counter(deps.storage, &counter);
+
+    Ok(Response::new()
+        .add_message(refund_msg(&counter)?)
+        .add_attribute("action", "withdraw_counter")
+        .add_attribute("counter_id", counter_id.to_string()))
+}
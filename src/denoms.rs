@@ -0,0 +1,145 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{InvalidIbcChannel, UnapprovedIbcChannel, UnknownIbcDenom};
+use crate::execute::assert_owner;
+use crate::ibc::is_valid_channel;
+use crate::msg::DenomInfo;
+use crate::state::{APPROVED_IBC_CHANNELS, REQUIRE_APPROVED_IBC_CHANNELS};
+use cosmwasm_schema::serde::Deserialize;
+use cosmwasm_std::{
+    DepsMut, Empty, MessageInfo, QuerierWrapper, QueryRequest, Response, StdResult, Storage,
+};
+use osmosis_std::types::cosmos::bank::v1beta1::BankQuerier;
+use prost::Message;
+
+// osmosis-std does not ship the ICS20 query types either, so the trace query is defined here
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct QueryDenomTraceRequest {
+    #[prost(string, tag = "1")]
+    pub hash: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+pub struct QueryDenomTraceResponse {
+    pub denom_trace: Option<DenomTrace>,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+pub struct DenomTrace {
+    pub path: String,
+    pub base_denom: String,
+}
+
+// Resolves the IBC path of a denom, (empty path, denom) for native denoms
+pub fn denom_trace(querier: &QuerierWrapper, denom: &str) -> Result<DenomTrace, ContractError> {
+    let Some(hash) = denom.strip_prefix("ibc/") else {
+        return Ok(DenomTrace {
+            path: String::new(),
+            base_denom: denom.to_string(),
+        });
+    };
+
+    let res: QueryDenomTraceResponse = querier
+        .query(&QueryRequest::<Empty>::Stargate {
+            path: "/ibc.applications.transfer.v1.Query/DenomTrace".to_string(),
+            data: QueryDenomTraceRequest {
+                hash: hash.to_string(),
+            }
+            .encode_to_vec()
+            .into(),
+        })
+        .map_err(|_| UnknownIbcDenom {
+            denom: denom.to_string(),
+        })?;
+
+    res.denom_trace.ok_or_else(|| UnknownIbcDenom {
+        denom: denom.to_string(),
+    })
+}
+
+// Trace and bank metadata of a denom, for showing amounts in human units
+pub fn denom_info(querier: &QuerierWrapper, denom: &str) -> StdResult<DenomInfo> {
+    let trace = denom_trace(querier, denom).ok();
+
+    // Denoms without registered metadata make the bank query fail
+    let metadata = BankQuerier::new(querier)
+        .denom_metadata(denom.to_string())
+        .ok()
+        .and_then(|res| res.metadata);
+    let decimals = metadata.as_ref().and_then(|metadata| {
+        metadata
+            .denom_units
+            .iter()
+            .find(|unit| unit.denom == metadata.display)
+            .map(|unit| unit.exponent)
+    });
+
+    Ok(DenomInfo {
+        denom: denom.to_string(),
+        base_denom: trace
+            .as_ref()
+            .map_or_else(|| denom.to_string(), |trace| trace.base_denom.clone()),
+        path: trace.map(|trace| trace.path).unwrap_or_default(),
+        display: metadata.map(|metadata| metadata.display),
+        decimals,
+    })
+}
+
+// When approval is required, IBC denoms must have arrived over an approved channel
+pub fn assert_approved_ibc_denoms(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    denoms: &[&str],
+) -> Result<(), ContractError> {
+    if !REQUIRE_APPROVED_IBC_CHANNELS
+        .may_load(storage)?
+        .unwrap_or_default()
+    {
+        return Ok(());
+    }
+
+    for denom in denoms.iter().filter(|denom| denom.starts_with("ibc/")) {
+        let trace = denom_trace(querier, denom)?;
+        // The first hop of the path is the channel the denom entered this chain through
+        let channel = trace.path.split('/').nth(1).unwrap_or_default();
+        if !APPROVED_IBC_CHANNELS.has(storage, channel) {
+            return Err(UnapprovedIbcChannel {
+                denom: denom.to_string(),
+                channel: channel.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn update_ibc_channels(
+    deps: DepsMut,
+    info: MessageInfo,
+    require_approved: Option<bool>,
+    approve: Vec<String>,
+    revoke: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if let Some(require_approved) = require_approved {
+        REQUIRE_APPROVED_IBC_CHANNELS.save(deps.storage, &require_approved)?;
+    }
+    for channel in &approve {
+        if !is_valid_channel(channel) {
+            return Err(InvalidIbcChannel {
+                channel: channel.clone(),
+            });
+        }
+        APPROVED_IBC_CHANNELS.save(deps.storage, channel, &true)?;
+    }
+    for channel in &revoke {
+        APPROVED_IBC_CHANNELS.remove(deps.storage, channel);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_ibc_channels")
+        .add_attribute("approved", approve.join(","))
+        .add_attribute("revoked", revoke.join(",")))
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use crate::msg::{HoldingRequirement, Operation};
+use cosmwasm_std::{Coin, StdError, Timestamp, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -17,4 +18,288 @@ pub enum ContractError {
 
     #[error("Invalid taker")]
     InvalidTaker {},
+
+    #[error("Invalid IBC channel: {channel}")]
+    InvalidIbcChannel { channel: String },
+
+    #[error("Unknown IBC denom: {denom}")]
+    UnknownIbcDenom { denom: String },
+
+    #[error("IBC denom {denom} did not arrive over an approved channel: {channel}")]
+    UnapprovedIbcChannel { denom: String, channel: String },
+
+    #[error("No IBC transfer found")]
+    NoIbcTransferFound {},
+
+    #[error("IBC transfer is still in flight")]
+    IbcTransferPending {},
+
+    #[error("Only the owner can perform this action")]
+    NotOwner {},
+
+    #[error("Only the proposed owner can accept ownership")]
+    NotPendingOwner {},
+
+    #[error("Query limit must be greater than zero")]
+    InvalidQueryLimit {},
+
+    #[error("Fee of {fee_bps} bps exceeds the maximum of {max_fee_bps} bps")]
+    FeeTooHigh { fee_bps: u16, max_fee_bps: u16 },
+
+    #[error("Referral fee of {fee_bps} bps exceeds the maximum of {max_fee_bps} bps")]
+    ReferralFeeTooHigh { fee_bps: u16, max_fee_bps: u16 },
+
+    #[error("Staking is not enabled")]
+    StakingNotEnabled {},
+
+    #[error("Invalid staking token")]
+    InvalidStakingToken {},
+
+    #[error("Cannot unbond more than the bonded amount")]
+    InsufficientBond {},
+
+    #[error("No rewards to claim")]
+    NothingToClaim {},
+
+    #[error("Only the owner or guardian can perform this action")]
+    NotOwnerOrGuardian {},
+
+    #[error("Operation {operation} is paused")]
+    Paused { operation: Operation },
+
+    #[error("Denom {denom} is delisted")]
+    DenomDelisted { denom: String },
+
+    #[error("Offer amounts must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Maker and taker denoms must differ")]
+    IdenticalDenoms {},
+
+    #[error("Invalid denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Denom {denom} is not tradable")]
+    DenomNotAllowed { denom: String },
+
+    #[error("Market {maker_denom}/{taker_denom} is not enabled")]
+    MarketDisabled {
+        maker_denom: String,
+        taker_denom: String,
+    },
+
+    #[error("Invalid market {maker_denom}/{taker_denom}")]
+    InvalidMarket {
+        maker_denom: String,
+        taker_denom: String,
+    },
+
+    #[error("Maker amount is below the market minimum of {min_maker_amount}")]
+    BelowMinimumSize { min_maker_amount: Uint128 },
+
+    #[error("Maker amount must be a multiple of the lot size {lot_size}")]
+    InvalidLotSize { lot_size: Uint128 },
+
+    #[error("Price per lot must be a multiple of the tick size {tick_size}")]
+    InvalidTickSize { tick_size: Uint128 },
+
+    #[error("Makers can have at most {max_open_offers} open offers")]
+    TooManyOpenOffers { max_open_offers: u32 },
+
+    #[error("Makers can create at most {max_offers} offers per window")]
+    CreationCapReached { max_offers: u32 },
+
+    #[error("Offer can't be cancelled before {cancellable_at}")]
+    OfferTooYoung { cancellable_at: Timestamp },
+
+    #[error("Offer has expired")]
+    OfferExpired {},
+
+    #[error("Invalid maker limits")]
+    InvalidMakerLimits {},
+
+    #[error("Settlement of {denom} is halted until its circuit breaker is reset")]
+    BreakerTripped { denom: String },
+
+    #[error("Invalid volume limit for {denom}")]
+    InvalidVolumeLimit { denom: String },
+
+    #[error("{participant} is not admitted by {gate}")]
+    NotAuthorizedParticipant { participant: String, gate: String },
+
+    #[error("Merkle root must be a hex encoded SHA-256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("Merkle proof must be hex encoded SHA-256 hashes")]
+    InvalidMerkleProof {},
+
+    #[error("Taker is not on the offer's allowlist")]
+    NotOnAllowlist {},
+
+    #[error("Taker has already taken {taken} of their {cap} cap")]
+    AllowlistCapExceeded { cap: Uint128, taken: Uint128 },
+
+    #[error("Invalid holding requirement: {requirement}")]
+    InvalidHoldingRequirement { requirement: HoldingRequirement },
+
+    #[error("Taker must hold {requirement}")]
+    HoldingRequirementNotMet { requirement: HoldingRequirement },
+
+    #[error("Invalid payout split: {reason}")]
+    InvalidPayoutSplit { reason: String },
+
+    #[error("Contract {contract} is not approved for proceeds actions")]
+    ActionContractNotApproved { contract: String },
+
+    #[error("Invalid proceeds action: {reason}")]
+    InvalidProceedsAction { reason: String },
+
+    #[error("Invalid approval offer: {reason}")]
+    InvalidApprovalOffer { reason: String },
+
+    #[error("Invalid application: {reason}")]
+    InvalidApplication { reason: String },
+
+    #[error("Already applied to this offer")]
+    AlreadyApplied {},
+
+    #[error("No application found for this applicant")]
+    NoApplicationFound {},
+
+    #[error("Application has lapsed")]
+    ApplicationLapsed {},
+
+    #[error("Offer already has the maximum of {max} applicants")]
+    TooManyApplicants { max: u32 },
+
+    #[error("Offers can't be reserved")]
+    ReservationsDisabled {},
+
+    #[error("Offer can only be taken by committing to it first")]
+    CommitRevealRequired {},
+
+    #[error("Offers can't be committed to")]
+    CommitmentsDisabled {},
+
+    #[error("Invalid commitment config")]
+    InvalidCommitmentConfig {},
+
+    #[error("Invalid reveal delay: {reason}")]
+    InvalidRevealDelay { reason: String },
+
+    #[error("Commitment must be a hex encoded sha256 hash")]
+    InvalidCommitment {},
+
+    #[error("Commitment was already made")]
+    CommitmentExists {},
+
+    #[error("No commitment found for the revealed take")]
+    NoCommitmentFound {},
+
+    #[error("Commitment has expired")]
+    CommitmentExpired {},
+
+    #[error("Commitment can be revealed from block {reveal_at_height}")]
+    CommitmentNotMatured { reveal_at_height: u64 },
+
+    #[error("Offers take up to {max} pending commitments")]
+    TooManyCommitments { max: u32 },
+
+    #[error(
+        "An earlier commitment may be revealed for this offer until block {expires_at_height}"
+    )]
+    CommitmentNotEarliest { expires_at_height: u64 },
+
+    #[error("Offers have to be made with a bond of at least {bond}")]
+    MakerBondRequired { bond: Coin },
+
+    #[error("Invalid maker bond: {reason}")]
+    InvalidMakerBond { reason: String },
+
+    #[error("Invalid maker bond config")]
+    InvalidMakerBondConfig {},
+
+    #[error("Module account {address} cannot receive settlement payouts")]
+    ModuleAccountPayout { address: String },
+
+    #[error("Invalid reservation config")]
+    InvalidReservationConfig {},
+
+    #[error("Offers can be reserved for 1 to {max_blocks} blocks")]
+    InvalidReservationBlocks { max_blocks: u64 },
+
+    #[error("Offer is reserved by {reserver} until block {expires_at_height}")]
+    OfferReserved {
+        reserver: String,
+        expires_at_height: u64,
+    },
+
+    #[error("No counter found from provided counter id")]
+    NoCounterFound {},
+
+    #[error("Counter has expired")]
+    CounterExpired {},
+
+    #[error("Only the proposer can withdraw a counter")]
+    NotCounterProposer {},
+
+    #[error("Invalid counter: {reason}")]
+    InvalidCounter { reason: String },
+
+    #[error("Only contracts can make contract offers")]
+    NotMakerContract {},
+
+    #[error("Invalid contract offer: {reason}")]
+    InvalidContractOffer { reason: String },
+
+    #[error("Fill exceeds the {max_maker_amount} the maker quotes")]
+    QuoteSizeExceeded { max_maker_amount: Uint128 },
+
+    #[error("Quote pays {maker_amount}, less than the minimum of {min_maker_amount}")]
+    SlippageExceeded {
+        maker_amount: Uint128,
+        min_maker_amount: Uint128,
+    },
+
+    #[error("Maker contract did not deliver {maker_amount} for offer {offer_id}")]
+    FillNotDelivered {
+        offer_id: String,
+        maker_amount: Uint128,
+    },
+
+    #[error("A flash take is already in progress")]
+    FlashInProgress {},
+
+    #[error("No flash take of this offer is in progress")]
+    NoFlashInProgress {},
+
+    #[error("Flash take of offer {offer_id} was not repaid with {taker_coin}")]
+    FlashNotRepaid { offer_id: String, taker_coin: Coin },
+
+    #[error("Takes with a callback can't send the maker coin to a recipient")]
+    CallbackWithRecipient {},
+
+    #[error("Only the maker can cancel an offer")]
+    NotOfferMaker {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Cannot migrate a {contract} contract")]
+    WrongContract { contract: String },
+
+    #[error("Cannot migrate from version {from} to older version {to}")]
+    MigrationDowngrade { from: String, to: String },
+
+    #[error("Migration invariant violated: {reason}")]
+    MigrationInvariant { reason: String },
+
+    #[error("A config is required to migrate from v1")]
+    MissingMigrationConfig {},
+
+    #[error("A migration is in progress")]
+    MigrationInProgress {},
+
+    #[error("No migration in progress")]
+    NoMigrationInProgress {},
 }
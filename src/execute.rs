@@ -1,25 +1,141 @@
+use crate::actions::{proceeds_action_msgs, validate_proceeds_action};
+use crate::allowlist::{take_from_allowlist, validate_allowlist};
+use crate::approvals::apply;
+use crate::breakers::{assert_breakers_closed, record_volume};
+use crate::callbacks::{assert_no_flash_in_progress, callback_msg};
+use crate::commitments::validate_reveal_delay;
+use crate::denoms::assert_approved_ibc_denoms;
 use crate::error::ContractError;
-use crate::error::ContractError::{InaccurateFunds, InvalidTaker, NoOfferFound, Unauthorized};
-use crate::msg::{ExecuteMsg, Offer};
-use crate::state::{FULFILLED_OFFERS, OFFERS, OFFER_ID_COUNTER};
-use cosmwasm_std::{BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response};
+use crate::error::ContractError::{
+    CallbackWithRecipient, CommitRevealRequired, InaccurateFunds, InsufficientBond,
+    InvalidApprovalOffer, InvalidPayoutSplit, InvalidProceedsAction, InvalidQueryLimit,
+    InvalidStakingToken, InvalidTaker, ModuleAccountPayout, NoOfferFound, NotOfferMaker, NotOwner,
+    NotPendingOwner, NothingToClaim, OfferExpired, OfferTooYoung, StakingNotEnabled, Unauthorized,
+};
+use crate::fees::{
+    calculate_settlement_fees, fee_bps_for_pair, record_referral_fee, save_pair_fees,
+    validate_fee_bps, validate_referral,
+};
+use crate::gates::{assert_authorized, validate_access_gate};
+use crate::governance::assert_listed;
+use crate::holdings::{assert_holdings, validate_holding_requirements};
+use crate::ibc::{send_ibc_transfer, validate_ibc_receiver};
+use crate::limits::{close_offer, deposit_msg, open_offer};
+use crate::maker_bonds::{maker_coin_prepulled, post_bond, settlement_msg};
+use crate::markets::validate_market;
+use crate::migrate::assert_not_migrating;
+use crate::msg::{
+    AccessGate, AllowlistProof, ExecuteMsg, FeePayer, HoldingRequirement, IbcTransfer,
+    IbcTransferStatus, Offer, Operation, PairFee, Payout, ProceedsAction, ReceiveMsg, Referral,
+    ReferralFeeSource, StakingToken, TakerAllowlist, TakerCallback,
+};
+use crate::pause::assert_not_paused;
+use crate::payouts::{split_payout, validate_payout_split};
+use crate::reservations::assert_not_reserved;
+use crate::staking::{distribute_fee, settle_rewards, staking_token_transfer_msg};
+use crate::state::{
+    Config, FlashSettlement, PendingAction, BONDED, CONFIG, FLASH_SETTLEMENT, FULFILLED_OFFERS,
+    IBC_TAKERS, MAKER_LIMITS, OFFERS, OFFER_ID_COUNTER, PAIR_FEES, PENDING_OWNER, STAKER_REWARDS,
+    STAKING_TOKEN, TOTAL_BONDED,
+};
+use cosmwasm_std::{
+    coin, from_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Order,
+    QuerierWrapper, Response, StdResult, Storage, SubMsg, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use osmosis_std::types::cosmos::auth::v1beta1::AuthQuerier;
 use osmosis_std::types::cosmos::authz::v1beta1::MsgExec;
 use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
 use osmosis_std::types::cosmos::base::v1beta1::Coin as Coin2;
 use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContract;
 
+#[allow(clippy::too_many_arguments)]
 pub fn make_offer(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     maker_coin: Coin2,
     taker_coin: Coin2,
+    referral: Option<Referral>,
+    access_gate: Option<AccessGate>,
+    taker_allowlist: Option<TakerAllowlist>,
+    holding_requirements: Option<Vec<HoldingRequirement>>,
+    proceeds_recipient: Option<String>,
+    payout_split: Option<Vec<Payout>>,
+    proceeds_action: Option<ProceedsAction>,
+    approval_window: Option<u64>,
+    reveal_delay: Option<u64>,
+    bond: Option<Coin>,
 ) -> Result<Response, ContractError> {
-    // Validate that no funds are being sent since contract will take the funds from the account in the future
-    if !info.funds.is_empty() {
-        return Err(InaccurateFunds {});
+    assert_not_migrating(deps.storage)?;
+    assert_not_paused(
+        deps.storage,
+        Operation::Make,
+        &[&maker_coin.denom, &taker_coin.denom],
+    )?;
+    assert_listed(deps.storage, &[&maker_coin.denom, &taker_coin.denom])?;
+    assert_approved_ibc_denoms(
+        deps.storage,
+        &deps.querier,
+        &[&maker_coin.denom, &taker_coin.denom],
+    )?;
+    validate_market(
+        deps.storage,
+        &Coin::try_from(maker_coin.clone())?,
+        &Coin::try_from(taker_coin.clone())?,
+    )?;
+
+    let config = CONFIG.load(deps.storage)?;
+    if let Some(gate) = &config.access_gate {
+        assert_authorized(&deps.querier, gate, &info.sender, Operation::Make)?;
+    }
+    if let Some(gate) = &access_gate {
+        validate_access_gate(deps.api, gate)?;
+    }
+    let taker_allowlist = taker_allowlist.map(validate_allowlist).transpose()?;
+    if let Some(requirements) = &holding_requirements {
+        validate_holding_requirements(deps.api, requirements)?;
+    }
+    let proceeds_recipient = proceeds_recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?;
+    if let Some(split) = &payout_split {
+        if proceeds_recipient.is_some() {
+            return Err(InvalidPayoutSplit {
+                reason: "a proceeds recipient can't be combined with a split".to_string(),
+            });
+        }
+        validate_payout_split(deps.api, split)?;
+    }
+    if let Some(action) = &proceeds_action {
+        // Failed actions are refunded to the maker, so the proceeds have to be the maker's alone
+        if proceeds_recipient.is_some() || payout_split.is_some() {
+            return Err(InvalidProceedsAction {
+                reason: "can't be combined with a proceeds recipient or split".to_string(),
+            });
+        }
+        validate_proceeds_action(&deps, action)?;
+    }
+    if let Some(approval_window) = approval_window {
+        if approval_window == 0 {
+            return Err(InvalidApprovalOffer {
+                reason: "approval window must be positive".to_string(),
+            });
+        }
+        // Allowlist caps are tracked on take, before the maker has approved anyone
+        if taker_allowlist.is_some() {
+            return Err(InvalidApprovalOffer {
+                reason: "approval offers can't have a taker allowlist".to_string(),
+            });
+        }
     }
 
+    if let Some(reveal_delay) = reveal_delay {
+        validate_reveal_delay(deps.storage, reveal_delay)?;
+    }
+
+    let maker_referral = validate_referral(deps.api, &config.fees, referral)?;
+
     // Initialize or load the offer_id counter
     let offer_id = match OFFER_ID_COUNTER.may_load(deps.storage)? {
         Some(counter) => counter + 1,
@@ -31,6 +147,22 @@ pub fn make_offer(
 
     OFFER_ID_COUNTER.save(deps.storage, &offer_id)?;
 
+    // No funds are sent besides an anti-spam deposit and bond, since the contract takes the maker coin at settlement
+    let funds = post_bond(
+        deps.storage,
+        &info.sender,
+        offer_id,
+        bond.as_ref(),
+        &info.funds,
+    )?;
+    let expires_at = open_offer(
+        deps.storage,
+        &env,
+        &info.sender,
+        &offer_id.to_string(),
+        &funds,
+    )?;
+
     // Store the offer
     OFFERS.save(
         deps.storage,
@@ -40,59 +172,264 @@ pub fn make_offer(
             taker: None,
             maker_coin: maker_coin.clone(),
             taker_coin: taker_coin.clone(),
+            fee: None,
+            maker_referral,
+            taker_referral: None,
+            created_at: Some(env.block.time),
+            expires_at,
+            deposit: funds.first().cloned().map(Coin2::from),
+            access_gate,
+            taker_allowlist,
+            holding_requirements,
+            proceeds_recipient: proceeds_recipient.as_ref().map(Addr::to_string),
+            taker_recipient: None,
+            payout_split,
+            taker_callback: None,
+            proceeds_action,
+            approval_window,
+            reservation: None,
+            reveal_delay,
+            bond: bond.map(Coin2::from),
         },
     )?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("offer_id", offer_id.to_string())
         .add_attribute("maker_coin", maker_coin.denom)
-        .add_attribute("taker_coin", taker_coin.denom.clone()))
+        .add_attribute("taker_coin", taker_coin.denom.clone());
+    if let Some(recipient) = proceeds_recipient {
+        response = response.add_attribute("proceeds_recipient", recipient);
+    }
+
+    Ok(response)
 }
+#[allow(clippy::too_many_arguments)]
 pub fn provide_taker(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     offer_id: String,
+    referral: Option<Referral>,
+    allowlist_proof: Option<AllowlistProof>,
+    recipient: Option<String>,
+    callback: Option<TakerCallback>,
+) -> Result<Response, ContractError> {
+    // Takes sent in the clear could be front-run, so these offers are only taken by reveal
+    if OFFERS.load(deps.storage, &offer_id)?.reveal_delay.is_some() {
+        return Err(CommitRevealRequired {});
+    }
+
+    take_offer(
+        deps,
+        env,
+        info,
+        offer_id,
+        referral,
+        allowlist_proof,
+        recipient,
+        callback,
+    )
+}
+
+// Takes an offer for the sender, whether sent directly, over IBC or as a revealed commitment
+#[allow(clippy::too_many_arguments)]
+pub fn take_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    referral: Option<Referral>,
+    allowlist_proof: Option<AllowlistProof>,
+    recipient: Option<String>,
+    callback: Option<TakerCallback>,
 ) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    assert_no_flash_in_progress(deps.storage)?;
+
     let offer = OFFERS.load(deps.storage, &offer_id)?;
+    assert_not_paused(
+        deps.storage,
+        Operation::Take,
+        &[&offer.maker_coin.denom, &offer.taker_coin.denom],
+    )?;
+    assert_listed(
+        deps.storage,
+        &[&offer.maker_coin.denom, &offer.taker_coin.denom],
+    )?;
+    validate_market(
+        deps.storage,
+        &Coin::try_from(offer.maker_coin.clone())?,
+        &Coin::try_from(offer.taker_coin.clone())?,
+    )?;
+
+    if offer
+        .expires_at
+        .is_some_and(|expires_at| env.block.time >= expires_at)
+    {
+        return Err(OfferExpired {});
+    }
+    assert_not_reserved(&env, &offer, &info.sender)?;
+
+    // Takers have to pass the global gate and the offer's own
+    let gates = [
+        CONFIG.load(deps.storage)?.access_gate,
+        offer.access_gate.clone(),
+    ];
+    for gate in gates.iter().flatten() {
+        assert_authorized(&deps.querier, gate, &info.sender, Operation::Take)?;
+    }
+    if let Some(requirements) = &offer.holding_requirements {
+        assert_holdings(&deps.querier, requirements, &info.sender)?;
+    }
+    if let Some(allowlist) = &offer.taker_allowlist {
+        take_from_allowlist(
+            deps.storage,
+            allowlist,
+            &info.sender,
+            allowlist_proof,
+            Coin::try_from(offer.maker_coin.clone())?.amount,
+        )?;
+    }
 
     // Validate maker address from storage
     deps.api.addr_validate(&offer.maker)?;
 
+    let taker_referral = validate_referral(deps.api, &CONFIG.load(deps.storage)?.fees, referral)?;
+    let recipient = recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?;
+    // Callbacks tell the taker it holds the maker coin, which it wouldn't with a recipient
+    if recipient.is_some() && callback.is_some() {
+        return Err(CallbackWithRecipient {});
+    }
+    // Bank refuses sends to module accounts, which would fail settlement on the taker's account
+    let payout_addresses = [
+        taker_referral
+            .as_ref()
+            .map(|referral| referral.address.as_str()),
+        recipient.as_ref().map(Addr::as_str),
+    ];
+    for address in payout_addresses.into_iter().flatten() {
+        assert_not_module_account(&deps.querier, address)?;
+    }
+
+    // Approval offers only settle once the maker picks an applicant
+    if let Some(approval_window) = offer.approval_window {
+        return apply(
+            deps.storage,
+            &env,
+            &info,
+            &offer_id,
+            &offer,
+            approval_window,
+            taker_referral,
+            recipient,
+            callback,
+        );
+    }
+
     // NOTE: could add in error handling to guard against already having a taker but not too urgent since offer is deleted when fulfilment msg is executed
     // Update offer to include new taker
     OFFERS.update(deps.storage, &offer_id, |offer| match offer {
         None => Err(NoOfferFound {}),
         Some(mut offer) => {
             offer.taker = Some(info.sender.to_string());
+            offer.taker_referral = taker_referral;
+            offer.taker_recipient = recipient.as_ref().map(Addr::to_string);
+            offer.taker_callback = callback.clone();
             Ok(offer)
         }
     })?;
 
     // Validate that the correct funds are being sent and ONLY the correct funds are being sent
     // Note: This could be optimized by using 'one_coin' and 'payable' from cw-utils but would need to update current result type
-    if info.funds.len() != 1 || info.funds[0] != Coin::try_from(offer.taker_coin.clone())? {
-        return Err(InaccurateFunds {});
-    }
+    // Flash takers pay during their callback instead
+    let flash = callback.as_ref().is_some_and(|callback| callback.flash);
+    let funds = match flash {
+        true if !info.funds.is_empty() => return Err(InaccurateFunds {}),
+        true => vec![],
+        false
+            if info.funds.len() != 1
+                || info.funds[0] != Coin::try_from(offer.taker_coin.clone())? =>
+        {
+            return Err(InaccurateFunds {})
+        }
+        false => vec![offer.taker_coin.clone()],
+    };
 
     // Now that the takers match is accepted by validating above funds,
     // the contract executes itself with the fulfilment message
+    let settlement = settlement_msg(
+        deps.storage,
+        &env,
+        &offer_id,
+        &offer,
+        info.sender.as_str(),
+        funds,
+    )?;
+
+    let mut response = Response::new()
+        .add_submessage(settlement)
+        .add_attribute("taker", info.sender.to_string())
+        .add_attribute("offer_id", offer_id.clone());
+    if let Some(recipient) = recipient {
+        response = response.add_attribute("recipient", recipient);
+    }
+
+    Ok(response)
+}
+
+pub fn assert_not_module_account(
+    querier: &QuerierWrapper,
+    address: &str,
+) -> Result<(), ContractError> {
+    // Addresses without an account yet can't be module accounts
+    let account = AuthQuerier::new(querier)
+        .account(address.to_string())
+        .ok()
+        .and_then(|response| response.account);
+    if account.is_some_and(|account| account.type_url == "/cosmos.auth.v1beta1.ModuleAccount") {
+        return Err(ModuleAccountPayout {
+            address: address.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Self-call settling an offer, forwarding the taker coin the contract received for it
+pub fn fulfill_offer_msg(env: &Env, offer_id: &str, funds: Vec<Coin2>) -> MsgExecuteContract {
     let msg = serde_json::to_vec(&ExecuteMsg::FulfillOffer {
-        offer_id: offer_id.clone(),
+        offer_id: offer_id.to_string(),
     })
     .unwrap();
 
-    let authz_wasm_msg = MsgExecuteContract {
-        sender: env.contract.address.to_string().parse().unwrap(),
-        contract: env.contract.address.to_string().parse().unwrap(),
+    MsgExecuteContract {
+        sender: env.contract.address.to_string(),
+        contract: env.contract.address.to_string(),
         msg,
-        funds: vec![offer.taker_coin.clone()],
-    };
+        funds,
+    }
+}
 
-    Ok(Response::new()
-        .add_message(authz_wasm_msg)
-        .add_attribute("taker", info.sender.to_string())
-        .add_attribute("offer_id", offer_id.clone()))
+// Entry for takers on other chains. ibc-hooks executes this from an intermediate sender
+// that holds the transferred funds, so the maker coin has to be sent back over IBC.
+pub fn provide_ibc_taker(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    channel: String,
+    receiver: String,
+    recovery_addr: Option<String>,
+) -> Result<Response, ContractError> {
+    let ibc_receiver = validate_ibc_receiver(&deps, channel, receiver, recovery_addr)?;
+    IBC_TAKERS.save(deps.storage, &offer_id, &ibc_receiver)?;
+
+    Ok(
+        provide_taker(deps, env, info, offer_id, None, None, None, None)?
+            .add_attribute("ibc_channel", ibc_receiver.channel)
+            .add_attribute("ibc_receiver", ibc_receiver.receiver),
+    )
 }
 
 pub fn fulfill_offer(
@@ -106,52 +443,524 @@ pub fn fulfill_offer(
     if info.sender != env.contract.address {
         return Err(Unauthorized {});
     }
+    // Catches settlements from within a flash callback that didn't go through an entry point
+    assert_no_flash_in_progress(deps.storage)?;
 
     let offer = OFFERS.load(deps.storage, &offer_id)?;
+    assert_not_paused(
+        deps.storage,
+        Operation::Settle,
+        &[&offer.maker_coin.denom, &offer.taker_coin.denom],
+    )?;
+    assert_breakers_closed(
+        deps.storage,
+        &[&offer.maker_coin.denom, &offer.taker_coin.denom],
+    )?;
 
     // Handle Option<String> type
     let taker = offer.taker.clone().ok_or(InvalidTaker {});
 
-    // Send funds on behalf of maker to taker
-    let send_msg = MsgSend {
-        from_address: offer.maker.clone(),
-        to_address: taker?,
-        amount: vec![offer.maker_coin.clone()],
+    // IBC takers are paid by routing the maker coin through the contract and back over IBC
+    let ibc_taker = IBC_TAKERS.may_load(deps.storage, &offer_id)?;
+    let maker_coin_recipient = match ibc_taker {
+        Some(_) => env.contract.address.to_string(),
+        None => match offer.taker_recipient.clone() {
+            Some(recipient) => recipient,
+            None => taker?,
+        },
     };
+    let maker_coin = Coin::try_from(offer.maker_coin.clone())?;
+    let taker_coin = Coin::try_from(offer.taker_coin.clone())?;
+    let breaker_events = record_volume(deps.storage, env.block.time, &[&maker_coin, &taker_coin])?;
 
-    let exec_msg = MsgExec {
-        grantee: env.contract.address.to_string(),
-        msgs: vec![send_msg.to_any()],
+    // The protocol fee and referral fees are deducted from the proceeds of the side configured to pay them
+    let fee_config = CONFIG.load(deps.storage)?.fees;
+    let fee_bps = fee_bps_for_pair(deps.storage, &maker_coin.denom, &taker_coin.denom)?;
+    let referrals = [offer.maker_referral.clone(), offer.taker_referral.clone()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let fee_base = match fee_config.fee_payer {
+        FeePayer::Maker => &taker_coin,
+        FeePayer::Taker => &maker_coin,
+    };
+    let fees = calculate_settlement_fees(
+        fee_base,
+        fee_bps,
+        &referrals,
+        &fee_config.referral_fee_source,
+    );
+    let proceeds = coin((fee_base.amount - fees.total).u128(), &fee_base.denom);
+    let (maker_coin_to_taker, taker_coin_to_maker) = match fee_config.fee_payer {
+        FeePayer::Maker => (maker_coin, proceeds),
+        FeePayer::Taker => (proceeds, taker_coin),
     };
+    let fee = fees.protocol_fee;
 
-    let msg = CosmosMsg::Stargate {
-        type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
-        value: Binary::from(exec_msg),
+    // Fees are shared with stakers when anyone is bonded, and kept by the contract until claimed
+    let fee_to_stakers = distribute_fee(deps.storage, &fee)?;
+    let fee_destination = match fee_to_stakers {
+        true => env.contract.address.to_string(),
+        false => fee_config.fee_recipient.to_string(),
     };
 
-    let coin = Coin::try_from(offer.taker_coin.clone())?;
+    let mut fee_payouts = vec![(fee_destination, fee.clone())];
+    for (referral, referral_fee) in &fees.referral_fees {
+        record_referral_fee(
+            deps.storage,
+            &Addr::unchecked(&referral.address),
+            referral_fee,
+        )?;
+        fee_payouts.push((referral.address.clone(), referral_fee.clone()));
+    }
+    fee_payouts.retain(|(_, payout)| !payout.amount.is_zero());
+
+    // Send funds on behalf of maker to taker
+    let mut maker_coin_payouts = vec![(maker_coin_recipient.clone(), maker_coin_to_taker.clone())];
 
-    // Send funds from contract to maker
-    let bank_message: CosmosMsg = BankMsg::Send {
-        to_address: offer.maker.clone(),
-        amount: vec![coin],
+    // Taker side fees are paid out of the maker coin, so they are pulled from the maker as well
+    if fee_config.fee_payer == FeePayer::Taker {
+        maker_coin_payouts.extend(fee_payouts.iter().cloned());
     }
-    .into();
+
+    // Bonded offers already pulled the maker coin into the contract, so it is paid out from there
+    let maker_coin_msgs: Vec<CosmosMsg> =
+        match maker_coin_prepulled(deps.storage, &offer_id, &offer) {
+            true => maker_coin_payouts
+                .into_iter()
+                .filter(|(to_address, _)| to_address != env.contract.address.as_str())
+                .map(|(to_address, payout)| {
+                    BankMsg::Send {
+                        to_address,
+                        amount: vec![payout],
+                    }
+                    .into()
+                })
+                .collect(),
+            false => {
+                let exec_msg = MsgExec {
+                    grantee: env.contract.address.to_string(),
+                    msgs: maker_coin_payouts
+                        .into_iter()
+                        .map(|(to_address, payout)| {
+                            MsgSend {
+                                from_address: offer.maker.clone(),
+                                to_address,
+                                amount: vec![payout.into()],
+                            }
+                            .to_any()
+                        })
+                        .collect(),
+                };
+                vec![CosmosMsg::Stargate {
+                    type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+                    value: Binary::from(exec_msg),
+                }]
+            }
+        };
+
+    // Send funds from contract to maker, or to the recipients the maker chose
+    let mut payouts = vec![];
+    let taker_coin_payouts = match (&offer.payout_split, &offer.proceeds_action) {
+        (Some(split), _) => split_payout(&taker_coin_to_maker, split),
+        (None, Some(action)) => {
+            payouts = proceeds_action_msgs(
+                deps.storage,
+                &env,
+                PendingAction {
+                    offer_id: offer_id.clone(),
+                    maker: offer.maker.clone(),
+                    action: action.clone(),
+                    proceeds: taker_coin_to_maker,
+                },
+            )?;
+            vec![]
+        }
+        (None, None) => {
+            let recipient = offer.proceeds_recipient.as_ref().unwrap_or(&offer.maker);
+            vec![(recipient.clone(), taker_coin_to_maker)]
+        }
+    };
 
     // Mark offer as fulfilled in case it needs to be referenced later
     FULFILLED_OFFERS.save(
         deps.storage,
         &offer_id.to_string(),
         &Offer {
-            maker: offer.maker.clone(),
-            taker: offer.taker.clone(),
-            maker_coin: offer.maker_coin.clone(),
-            taker_coin: offer.taker_coin.clone(),
+            fee: Some(fee.clone().into()),
+            ..offer.clone()
         },
     )?;
 
     // Delete offer from active offers
-    OFFERS.remove(deps.storage, &offer_id);
+    let refunds = close_offer(deps.storage, &env, &offer_id, &offer)?;
+
+    let mut response = Response::new()
+        .add_messages(maker_coin_msgs)
+        .add_messages(refunds)
+        .add_events(breaker_events);
+    for (to_address, payout) in taker_coin_payouts {
+        response = response.add_attribute("taker_coin_recipient", &to_address);
+        payouts.push(SubMsg::new(BankMsg::Send {
+            to_address,
+            amount: vec![payout],
+        }));
+    }
+
+    // Maker side fees are paid out of the taker coin the contract is holding
+    if fee_config.fee_payer == FeePayer::Maker {
+        for (to_address, payout) in fee_payouts {
+            if to_address != env.contract.address {
+                payouts.push(SubMsg::new(BankMsg::Send {
+                    to_address,
+                    amount: vec![payout],
+                }));
+            }
+        }
+    }
+
+    // Flash takers only hold the taker coin once they repay during the callback, so the payouts
+    // wait for its reply
+    match &offer.taker_callback {
+        Some(callback) => {
+            let taker = offer.taker.clone().ok_or(InvalidTaker {})?;
+            let taker_coin = Coin::try_from(offer.taker_coin.clone())?;
+            if callback.flash {
+                FLASH_SETTLEMENT.save(
+                    deps.storage,
+                    &FlashSettlement {
+                        offer_id: offer_id.clone(),
+                        taker_coin: taker_coin.clone(),
+                        repaid: false,
+                        payouts,
+                    },
+                )?;
+            } else {
+                response = response.add_submessages(payouts);
+            }
+            response = response.add_submessage(callback_msg(
+                &offer_id,
+                &taker,
+                &maker_coin_to_taker,
+                &taker_coin,
+                callback,
+            )?);
+        }
+        None => response = response.add_submessages(payouts),
+    }
+
+    if let Some(refund) = deposit_msg(&offer, &offer.maker)? {
+        response = response.add_message(refund);
+    }
+
+    for (referral, referral_fee) in &fees.referral_fees {
+        response = response
+            .add_attribute("referrer", &referral.address)
+            .add_attribute("referral_fee", referral_fee.to_string());
+    }
+
+    if let Some(receiver) = ibc_taker {
+        let transfer = IbcTransfer {
+            offer_id: offer_id.clone(),
+            receiver,
+            coin: maker_coin_to_taker.into(),
+            attempts: 1,
+            status: IbcTransferStatus::Pending { sequence: 0 },
+        };
+        response = response.add_submessage(send_ibc_transfer(deps.storage, &env, transfer)?);
+    }
 
-    Ok(Response::new().add_message(msg).add_message(bank_message).add_attribute("offer_fulfilled", offer_id.to_string()))
+    Ok(response
+        .add_attribute("offer_fulfilled", offer_id.to_string())
+        .add_attribute("maker_coin_recipient", maker_coin_recipient)
+        .add_attribute("fee", fee.to_string())
+        .add_attribute(
+            "fee_payer",
+            format!("{:?}", fee_config.fee_payer).to_lowercase(),
+        )
+        .add_attribute("fee_to_stakers", fee_to_stakers.to_string()))
+}
+
+// Offers hold no escrow besides any anti-spam deposit, which is refunded. Never paused, so makers can always withdraw.
+pub fn cancel_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    let offer = OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    if offer.maker != info.sender {
+        return Err(NotOfferMaker {});
+    }
+
+    let min_offer_lifetime = MAKER_LIMITS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .min_offer_lifetime;
+    if let Some(created_at) = offer.created_at {
+        let cancellable_at = created_at.plus_seconds(min_offer_lifetime);
+        if env.block.time < cancellable_at {
+            return Err(OfferTooYoung { cancellable_at });
+        }
+    }
+
+    let refunds = close_offer(deps.storage, &env, &offer_id, &offer)?;
+
+    let mut response = Response::new().add_messages(refunds);
+    if let Some(refund) = deposit_msg(&offer, &offer.maker)? {
+        response = response.add_message(refund);
+    }
+
+    Ok(response
+        .add_attribute("action", "cancel_offer")
+        .add_attribute("offer_id", offer_id))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_bps: Option<u16>,
+    fee_recipient: Option<String>,
+    fee_payer: Option<FeePayer>,
+    pair_fees: Option<Vec<PairFee>>,
+    max_referral_fee_bps: Option<u16>,
+    referral_fee_source: Option<ReferralFeeSource>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_owner(deps.storage, &info.sender)?;
+    let fees = &mut config.fees;
+
+    if let Some(fee_bps) = fee_bps {
+        validate_fee_bps(fee_bps)?;
+        fees.fee_bps = fee_bps;
+    }
+    if let Some(fee_recipient) = fee_recipient {
+        fees.fee_recipient = deps.api.addr_validate(&fee_recipient)?;
+    }
+    if let Some(fee_payer) = fee_payer {
+        fees.fee_payer = fee_payer;
+    }
+    if let Some(max_referral_fee_bps) = max_referral_fee_bps {
+        validate_fee_bps(max_referral_fee_bps)?;
+        fees.max_referral_fee_bps = max_referral_fee_bps;
+    }
+    if let Some(referral_fee_source) = referral_fee_source {
+        fees.referral_fee_source = referral_fee_source;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    if let Some(pair_fees) = pair_fees {
+        PAIR_FEES.clear(deps.storage);
+        save_pair_fees(deps.storage, &pair_fees)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_fees")
+        .add_attribute("fee_bps", config.fees.fee_bps.to_string())
+        .add_attribute("fee_recipient", config.fees.fee_recipient))
+}
+
+// Loads the config, failing unless the sender is the current owner
+pub fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<Config, ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.owner.as_ref() != Some(sender) {
+        return Err(NotOwner {});
+    }
+    Ok(config)
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_query_limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_owner(deps.storage, &info.sender)?;
+
+    if let Some(max_query_limit) = max_query_limit {
+        if max_query_limit == 0 {
+            return Err(InvalidQueryLimit {});
+        }
+        config.max_query_limit = max_query_limit;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("max_query_limit", config.max_query_limit.to_string()))
+}
+
+// Ownership moves in two steps so a typo in the new owner's address can't lock the contract
+pub fn propose_new_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let pending_owner = deps.api.addr_validate(&owner)?;
+    PENDING_OWNER.save(deps.storage, &pending_owner)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_owner")
+        .add_attribute("pending_owner", pending_owner))
+}
+
+pub fn accept_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if PENDING_OWNER.may_load(deps.storage)?.as_ref() != Some(&info.sender) {
+        return Err(NotPendingOwner {});
+    }
+
+    PENDING_OWNER.remove(deps.storage);
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.owner = Some(info.sender.clone());
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("owner", info.sender))
+}
+
+// Leaves the contract without an owner, permanently freezing everything only the owner can change
+pub fn renounce_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config = assert_owner(deps.storage, &info.sender)?;
+
+    config.owner = None;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_ownership")
+        .add_attribute("previous_owner", info.sender))
+}
+
+pub fn update_guardian(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_owner(deps.storage, &info.sender)?;
+
+    config.guardian = guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_guardian")
+        .add_attribute(
+            "guardian",
+            config
+                .guardian
+                .map_or_else(|| "none".to_string(), |guardian| guardian.to_string()),
+        ))
+}
+
+pub fn bond(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let denom = match STAKING_TOKEN.may_load(deps.storage)? {
+        None => return Err(StakingNotEnabled {}),
+        Some(StakingToken::Cw20 { .. }) => return Err(InvalidStakingToken {}),
+        Some(StakingToken::Native { denom }) => denom,
+    };
+
+    // Only the staking token may be sent
+    if info.funds.len() != 1 || info.funds[0].denom != denom || info.funds[0].amount.is_zero() {
+        return Err(InaccurateFunds {});
+    }
+
+    bond_tokens(deps, info.sender, info.funds[0].amount)
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // The sender of a CW20 hook is the token contract itself
+    match STAKING_TOKEN.may_load(deps.storage)? {
+        None => return Err(StakingNotEnabled {}),
+        Some(StakingToken::Cw20 { contract_addr }) if contract_addr == info.sender => {}
+        Some(_) => return Err(InvalidStakingToken {}),
+    }
+
+    match from_binary(&msg.msg)? {
+        ReceiveMsg::Bond {} => {
+            let staker = deps.api.addr_validate(&msg.sender)?;
+            bond_tokens(deps, staker, msg.amount)
+        }
+    }
+}
+
+fn bond_tokens(deps: DepsMut, staker: Addr, amount: Uint128) -> Result<Response, ContractError> {
+    let bonded = settle_rewards(deps.storage, &staker)?;
+    BONDED.save(deps.storage, &staker, &(bonded + amount))?;
+
+    let total_bonded = TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default();
+    TOTAL_BONDED.save(deps.storage, &(total_bonded + amount))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bond")
+        .add_attribute("staker", staker)
+        .add_attribute("amount", amount))
+}
+
+pub fn unbond(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let token = STAKING_TOKEN
+        .may_load(deps.storage)?
+        .ok_or(StakingNotEnabled {})?;
+
+    let bonded = settle_rewards(deps.storage, &info.sender)?;
+    if amount.is_zero() || amount > bonded {
+        return Err(InsufficientBond {});
+    }
+
+    BONDED.save(deps.storage, &info.sender, &(bonded - amount))?;
+    TOTAL_BONDED.update(deps.storage, |total| -> StdResult<_> { Ok(total - amount) })?;
+
+    Ok(Response::new()
+        .add_message(staking_token_transfer_msg(&token, &info.sender, amount)?)
+        .add_attribute("action", "unbond")
+        .add_attribute("staker", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn claim_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    settle_rewards(deps.storage, &info.sender)?;
+
+    // Rewards are keyed by denom, so they come out in the sorted order bank sends require
+    let rewards = STAKER_REWARDS
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if rewards.is_empty() {
+        return Err(NothingToClaim {});
+    }
+
+    for reward in &rewards {
+        STAKER_REWARDS.remove(deps.storage, (&info.sender, &reward.denom));
+    }
+
+    let claimed = rewards
+        .iter()
+        .map(|reward| reward.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: rewards,
+        })
+        .add_attribute("action", "claim_rewards")
+        .add_attribute("staker", info.sender)
+        .add_attribute("rewards", claimed))
 }
@@ -0,0 +1,125 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{FeeTooHigh, ReferralFeeTooHigh};
+use crate::msg::{PairFee, Referral, ReferralFeeSource};
+use crate::state::{FeeConfig, CONFIG, PAIR_FEES, REFERRED_TRADES, REFERRER_EARNINGS};
+use cosmwasm_std::{Addr, Api, Coin, StdResult, Storage, Uint128};
+
+pub const MAX_FEE_BPS: u16 = 1_000;
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+pub fn validate_fee_bps(fee_bps: u16) -> Result<(), ContractError> {
+    if fee_bps > MAX_FEE_BPS {
+        return Err(FeeTooHigh {
+            fee_bps,
+            max_fee_bps: MAX_FEE_BPS,
+        });
+    }
+    Ok(())
+}
+
+pub fn save_pair_fees(
+    storage: &mut dyn Storage,
+    pair_fees: &[PairFee],
+) -> Result<(), ContractError> {
+    for pair_fee in pair_fees {
+        validate_fee_bps(pair_fee.fee_bps)?;
+        PAIR_FEES.save(
+            storage,
+            (&pair_fee.maker_denom, &pair_fee.taker_denom),
+            &pair_fee.fee_bps,
+        )?;
+    }
+    Ok(())
+}
+
+// Pair overrides take precedence over the global fee
+pub fn fee_bps_for_pair(
+    storage: &dyn Storage,
+    maker_denom: &str,
+    taker_denom: &str,
+) -> StdResult<u16> {
+    match PAIR_FEES.may_load(storage, (maker_denom, taker_denom))? {
+        Some(fee_bps) => Ok(fee_bps),
+        None => Ok(CONFIG.load(storage)?.fees.fee_bps),
+    }
+}
+
+// Fees round down so the paying side never loses more than the configured rate
+pub fn calculate_fee(coin: &Coin, fee_bps: u16) -> Coin {
+    Coin {
+        denom: coin.denom.clone(),
+        amount: coin
+            .amount
+            .multiply_ratio(Uint128::from(fee_bps), BPS_DENOMINATOR),
+    }
+}
+
+pub fn validate_referral(
+    api: &dyn Api,
+    config: &FeeConfig,
+    referral: Option<Referral>,
+) -> Result<Option<Referral>, ContractError> {
+    let Some(referral) = referral else {
+        return Ok(None);
+    };
+
+    api.addr_validate(&referral.address)?;
+    if referral.fee_bps > config.max_referral_fee_bps {
+        return Err(ReferralFeeTooHigh {
+            fee_bps: referral.fee_bps,
+            max_fee_bps: config.max_referral_fee_bps,
+        });
+    }
+
+    Ok(Some(referral))
+}
+
+pub struct SettlementFees {
+    pub protocol_fee: Coin,
+    pub referral_fees: Vec<(Referral, Coin)>,
+    /// Everything deducted from the fee paying side's proceeds
+    pub total: Uint128,
+}
+
+pub fn calculate_settlement_fees(
+    base: &Coin,
+    fee_bps: u16,
+    referrals: &[Referral],
+    source: &ReferralFeeSource,
+) -> SettlementFees {
+    let mut protocol_fee = calculate_fee(base, fee_bps);
+    let mut referral_fees = vec![];
+
+    for referral in referrals {
+        let mut referral_fee = calculate_fee(base, referral.fee_bps);
+        if *source == ReferralFeeSource::ProtocolFee {
+            referral_fee.amount = referral_fee.amount.min(protocol_fee.amount);
+            protocol_fee.amount -= referral_fee.amount;
+        }
+        referral_fees.push((referral.clone(), referral_fee));
+    }
+
+    let total = referral_fees
+        .iter()
+        .fold(protocol_fee.amount, |total, (_, fee)| total + fee.amount);
+
+    SettlementFees {
+        protocol_fee,
+        referral_fees,
+        total,
+    }
+}
+
+pub fn record_referral_fee(
+    storage: &mut dyn Storage,
+    referrer: &Addr,
+    fee: &Coin,
+) -> StdResult<()> {
+    REFERRER_EARNINGS.update(storage, (referrer, &fee.denom), |earned| -> StdResult<_> {
+        Ok(earned.unwrap_or_default() + fee.amount)
+    })?;
+    REFERRED_TRADES.update(storage, referrer, |trades| -> StdResult<_> {
+        Ok(trades.unwrap_or_default() + 1)
+    })?;
+    Ok(())
+}
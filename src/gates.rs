@@ -0,0 +1,88 @@
+use crate::error::ContractError;
+use crate::error::ContractError::NotAuthorizedParticipant;
+use crate::execute::assert_owner;
+use crate::msg::{AccessGate, GatekeeperQueryMsg, IsAuthorizedResponse, Operation};
+use crate::state::CONFIG;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, DepsMut, MessageInfo, QuerierWrapper, Response};
+
+// The one cw4 query used, defined here rather than depending on cw4 for it
+#[cw_serde]
+enum Cw4QueryMsg {
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+struct MemberResponse {
+    weight: Option<u64>,
+}
+
+// Fails unless the gate's contract admits the participant. Members of a cw4 group are admitted
+// whatever their weight.
+pub fn assert_authorized(
+    querier: &QuerierWrapper,
+    gate: &AccessGate,
+    participant: &Addr,
+    operation: Operation,
+) -> Result<(), ContractError> {
+    let authorized = match gate {
+        AccessGate::Cw4Group { contract } => {
+            let member: MemberResponse = querier.query_wasm_smart(
+                contract,
+                &Cw4QueryMsg::Member {
+                    addr: participant.to_string(),
+                    at_height: None,
+                },
+            )?;
+            member.weight.is_some()
+        }
+        AccessGate::Gatekeeper { contract } => {
+            let res: IsAuthorizedResponse = querier.query_wasm_smart(
+                contract,
+                &GatekeeperQueryMsg::IsAuthorized {
+                    participant: participant.to_string(),
+                    operation,
+                },
+            )?;
+            res.authorized
+        }
+    };
+
+    if !authorized {
+        return Err(NotAuthorizedParticipant {
+            participant: participant.to_string(),
+            gate: gate.contract().to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_access_gate(api: &dyn Api, gate: &AccessGate) -> Result<(), ContractError> {
+    api.addr_validate(gate.contract())?;
+    Ok(())
+}
+
+// Owner only. Sets the gate every maker and taker has to pass, removing it when unset.
+pub fn update_access_gate(
+    deps: DepsMut,
+    info: MessageInfo,
+    access_gate: Option<AccessGate>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_owner(deps.storage, &info.sender)?;
+    if let Some(gate) = &access_gate {
+        validate_access_gate(deps.api, gate)?;
+    }
+
+    let gate = access_gate
+        .as_ref()
+        .map_or(String::new(), |gate| gate.contract().to_string());
+    config.access_gate = access_gate;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_access_gate")
+        .add_attribute("access_gate", gate))
+}
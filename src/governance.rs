@@ -0,0 +1,115 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{DenomDelisted, NoOfferFound};
+use crate::limits::{close_offer, deposit_msg};
+use crate::migrate::assert_not_migrating;
+use crate::msg::{AuditEntry, Operation};
+use crate::pause::save_pause;
+use crate::state::{AUDIT_LOG, AUDIT_LOG_COUNTER, CONFIG, DELISTED_DENOMS, OFFERS, PENDING_OWNER};
+use cosmwasm_std::{DepsMut, Env, Event, Response, Storage};
+
+// Fails if any of the denoms was delisted by governance
+pub fn assert_listed(storage: &dyn Storage, denoms: &[&str]) -> Result<(), ContractError> {
+    match denoms
+        .iter()
+        .find(|denom| DELISTED_DENOMS.has(storage, denom))
+    {
+        Some(denom) => Err(DenomDelisted {
+            denom: denom.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn force_cancel_offer(
+    deps: DepsMut,
+    env: Env,
+    offer_id: String,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    let offer = OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    let refunds = close_offer(deps.storage, &env, &offer_id, &offer)?;
+
+    let event = Event::new("governance_force_cancel_offer")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("maker", &offer.maker);
+    let response = record_action(deps.storage, &env, event)?.add_messages(refunds);
+
+    // Governance cancelling an offer is no fault of the maker, so any deposit is refunded
+    Ok(match deposit_msg(&offer, &offer.maker)? {
+        Some(refund) => response.add_message(refund),
+        None => response,
+    })
+}
+
+pub fn delist_denom(deps: DepsMut, env: Env, denom: String) -> Result<Response, ContractError> {
+    DELISTED_DENOMS.save(deps.storage, &denom, &true)?;
+
+    let event = Event::new("governance_delist_denom").add_attribute("denom", denom);
+    record_action(deps.storage, &env, event)
+}
+
+pub fn relist_denom(deps: DepsMut, env: Env, denom: String) -> Result<Response, ContractError> {
+    DELISTED_DENOMS.remove(deps.storage, &denom);
+
+    let event = Event::new("governance_relist_denom").add_attribute("denom", denom);
+    record_action(deps.storage, &env, event)
+}
+
+pub fn governance_set_pause(
+    deps: DepsMut,
+    env: Env,
+    operation: Operation,
+    denom: Option<String>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    save_pause(deps.storage, &operation, denom.as_deref(), paused)?;
+
+    let event = Event::new("governance_set_pause")
+        .add_attribute("operation", operation.as_str())
+        .add_attribute("denom", denom.unwrap_or_else(|| "*".to_string()))
+        .add_attribute("paused", paused.to_string());
+    record_action(deps.storage, &env, event)
+}
+
+pub fn replace_owner(deps: DepsMut, env: Env, owner: String) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_owner = config.owner.replace(owner.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    let event = Event::new("governance_replace_owner")
+        .add_attribute(
+            "previous_owner",
+            previous_owner.map_or_else(|| "none".to_string(), |owner| owner.to_string()),
+        )
+        .add_attribute("owner", owner);
+    record_action(deps.storage, &env, event)
+}
+
+// Append the action's event to the audit log and emit it
+fn record_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    event: Event,
+) -> Result<Response, ContractError> {
+    let id = AUDIT_LOG_COUNTER.may_load(storage)?.unwrap_or_default() + 1;
+    AUDIT_LOG_COUNTER.save(storage, &id)?;
+    AUDIT_LOG.save(
+        storage,
+        id,
+        &AuditEntry {
+            id,
+            height: env.block.height,
+            time: env.block.time,
+            event: event.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_event(event.add_attribute("audit_id", id.to_string()))
+        .add_attribute("action", "sudo"))
+}
@@ -0,0 +1,91 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{HoldingRequirementNotMet, InvalidHoldingRequirement};
+use crate::markets::validate_denom;
+use crate::msg::HoldingRequirement;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, QuerierWrapper};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+
+// The one cw721 query used, defined here rather than depending on cw721 for it
+#[cw_serde]
+enum Cw721QueryMsg {
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+struct TokensResponse {
+    tokens: Vec<String>,
+}
+
+pub fn validate_holding_requirements(
+    api: &dyn Api,
+    requirements: &[HoldingRequirement],
+) -> Result<(), ContractError> {
+    for requirement in requirements {
+        let valid = match requirement {
+            HoldingRequirement::NativeBalance { denom, min_amount } => {
+                validate_denom(denom)?;
+                !min_amount.is_zero()
+            }
+            HoldingRequirement::Cw20Balance {
+                contract,
+                min_amount,
+            } => api.addr_validate(contract).is_ok() && !min_amount.is_zero(),
+            HoldingRequirement::Cw721Owner { contract } => api.addr_validate(contract).is_ok(),
+        };
+        if !valid {
+            return Err(InvalidHoldingRequirement {
+                requirement: requirement.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Fails with the first requirement the taker doesn't meet
+pub fn assert_holdings(
+    querier: &QuerierWrapper,
+    requirements: &[HoldingRequirement],
+    taker: &Addr,
+) -> Result<(), ContractError> {
+    for requirement in requirements {
+        let met = match requirement {
+            HoldingRequirement::NativeBalance { denom, min_amount } => {
+                querier.query_balance(taker, denom)?.amount >= *min_amount
+            }
+            HoldingRequirement::Cw20Balance {
+                contract,
+                min_amount,
+            } => {
+                let res: BalanceResponse = querier.query_wasm_smart(
+                    contract,
+                    &Cw20QueryMsg::Balance {
+                        address: taker.to_string(),
+                    },
+                )?;
+                res.balance >= *min_amount
+            }
+            HoldingRequirement::Cw721Owner { contract } => {
+                let res: TokensResponse = querier.query_wasm_smart(
+                    contract,
+                    &Cw721QueryMsg::Tokens {
+                        owner: taker.to_string(),
+                        start_after: None,
+                        limit: Some(1),
+                    },
+                )?;
+                !res.tokens.is_empty()
+            }
+        };
+        if !met {
+            return Err(HoldingRequirementNotMet {
+                requirement: requirement.clone(),
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{IbcTransferPending, InvalidIbcChannel, NoIbcTransferFound};
+use crate::msg::{IBCLifecycleComplete, IbcReceiver, IbcTransfer, IbcTransferStatus};
+use crate::state::{IBC_TRANSFERS, IBC_TRANSFER_SEQUENCES, INFLIGHT_IBC_TRANSFER};
+use cosmwasm_std::{
+    BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, Reply, Response, StdError, Storage, SubMsg,
+};
+use osmosis_std::types::cosmos::base::v1beta1::Coin as Coin2;
+use prost::Message;
+
+pub const IBC_TRANSFER_REPLY_ID: u64 = 1;
+// Automatic attempts made before a failed return transfer is refunded or parked for a manual retry
+pub const MAX_IBC_TRANSFER_ATTEMPTS: u32 = 3;
+pub const IBC_TRANSFER_TIMEOUT_SECONDS: u64 = 600;
+
+// osmosis-std does not ship the ICS20 types, so the two messages we need are defined here
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    pub source_port: String,
+    #[prost(string, tag = "2")]
+    pub source_channel: String,
+    #[prost(message, optional, tag = "3")]
+    pub token: Option<Coin2>,
+    #[prost(string, tag = "4")]
+    pub sender: String,
+    #[prost(string, tag = "5")]
+    pub receiver: String,
+    #[prost(uint64, tag = "7")]
+    pub timeout_timestamp: u64,
+    #[prost(string, tag = "8")]
+    pub memo: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct MsgTransferResponse {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+}
+
+pub fn is_valid_channel(channel: &str) -> bool {
+    channel
+        .strip_prefix("channel-")
+        .is_some_and(|id| id.parse::<u64>().is_ok())
+}
+
+pub fn validate_ibc_receiver(
+    deps: &DepsMut,
+    channel: String,
+    receiver: String,
+    recovery_addr: Option<String>,
+) -> Result<IbcReceiver, ContractError> {
+    if !is_valid_channel(&channel) {
+        return Err(InvalidIbcChannel { channel });
+    }
+
+    if receiver.is_empty() {
+        return Err(StdError::generic_err("IBC receiver cannot be empty").into());
+    }
+
+    // The recovery address lives on Osmosis, so it can be validated here
+    if let Some(addr) = &recovery_addr {
+        deps.api.addr_validate(addr)?;
+    }
+
+    Ok(IbcReceiver {
+        channel,
+        receiver,
+        recovery_addr,
+    })
+}
+
+pub fn transfer_msg(env: &Env, transfer: &IbcTransfer) -> CosmosMsg {
+    let msg = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: transfer.receiver.channel.clone(),
+        token: Some(transfer.coin.clone()),
+        sender: env.contract.address.to_string(),
+        receiver: transfer.receiver.receiver.clone(),
+        timeout_timestamp: env
+            .block
+            .time
+            .plus_seconds(IBC_TRANSFER_TIMEOUT_SECONDS)
+            .nanos(),
+        // Ask ibc-hooks to report the ack or timeout back to this contract through sudo
+        memo: format!(r#"{{"ibc_callback":"{}"}}"#, env.contract.address),
+    };
+
+    CosmosMsg::Stargate {
+        type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+        value: Binary::from(msg.encode_to_vec()),
+    }
+}
+
+// Builds the return transfer and records it until the MsgTransfer reply reports its sequence
+pub fn send_ibc_transfer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    transfer: IbcTransfer,
+) -> Result<SubMsg, ContractError> {
+    INFLIGHT_IBC_TRANSFER.save(storage, &transfer.offer_id)?;
+    IBC_TRANSFERS.save(storage, &transfer.offer_id, &transfer)?;
+
+    Ok(SubMsg::reply_on_success(
+        transfer_msg(env, &transfer),
+        IBC_TRANSFER_REPLY_ID,
+    ))
+}
+
+// Tracks a sent transfer by the sequence in its MsgTransfer reply, so its ack or timeout can be
+// matched back to it
+pub fn track_ibc_transfer(
+    storage: &mut dyn Storage,
+    mut transfer: IbcTransfer,
+    data: Option<Binary>,
+) -> Result<u64, ContractError> {
+    let data = data.ok_or_else(|| StdError::generic_err("MsgTransfer reply is missing data"))?;
+    let sequence = MsgTransferResponse::decode(data.as_slice())
+        .map_err(|err| StdError::parse_err("MsgTransferResponse", err))?
+        .sequence;
+
+    transfer.status = IbcTransferStatus::Pending { sequence };
+    IBC_TRANSFERS.save(storage, &transfer.offer_id, &transfer)?;
+    IBC_TRANSFER_SEQUENCES.save(
+        storage,
+        (&transfer.receiver.channel, sequence),
+        &transfer.offer_id,
+    )?;
+    Ok(sequence)
+}
+
+pub fn handle_ibc_transfer_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let offer_id = INFLIGHT_IBC_TRANSFER.load(deps.storage)?;
+    INFLIGHT_IBC_TRANSFER.remove(deps.storage);
+
+    let data = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data;
+    let transfer = IBC_TRANSFERS.load(deps.storage, &offer_id)?;
+    let sequence = track_ibc_transfer(deps.storage, transfer, data)?;
+
+    Ok(Response::new()
+        .add_attribute("ibc_transfer_offer_id", offer_id)
+        .add_attribute("ibc_transfer_sequence", sequence.to_string()))
+}
+
+pub fn ibc_lifecycle_complete(
+    deps: DepsMut,
+    env: Env,
+    lifecycle: IBCLifecycleComplete,
+) -> Result<Response, ContractError> {
+    let (channel, sequence, success) = match lifecycle {
+        IBCLifecycleComplete::IBCAck {
+            channel,
+            sequence,
+            success,
+            ..
+        } => (channel, sequence, success),
+        IBCLifecycleComplete::IBCTimeout { channel, sequence } => (channel, sequence, false),
+    };
+
+    let offer_id = IBC_TRANSFER_SEQUENCES
+        .may_load(deps.storage, (&channel, sequence))?
+        .ok_or(NoIbcTransferFound {})?;
+    IBC_TRANSFER_SEQUENCES.remove(deps.storage, (&channel, sequence));
+
+    let mut transfer = IBC_TRANSFERS.load(deps.storage, &offer_id)?;
+
+    if success {
+        IBC_TRANSFERS.remove(deps.storage, &offer_id);
+        return Ok(Response::new()
+            .add_attribute("ibc_transfer_completed", offer_id)
+            .add_attribute("receiver", transfer.receiver.receiver));
+    }
+
+    // Retry the transfer while attempts remain
+    if transfer.attempts < MAX_IBC_TRANSFER_ATTEMPTS {
+        transfer.attempts += 1;
+        let attempts = transfer.attempts;
+        let sub_msg = send_ibc_transfer(deps.storage, &env, transfer)?;
+        return Ok(Response::new()
+            .add_submessage(sub_msg)
+            .add_attribute("ibc_transfer_retried", offer_id)
+            .add_attribute("attempts", attempts.to_string()));
+    }
+
+    // Out of retries, refund to the taker's Osmosis recovery address when one was provided
+    if let Some(recovery_addr) = transfer.receiver.recovery_addr.clone() {
+        IBC_TRANSFERS.remove(deps.storage, &offer_id);
+        let refund_msg: CosmosMsg = BankMsg::Send {
+            to_address: recovery_addr.clone(),
+            amount: vec![Coin::try_from(transfer.coin)?],
+        }
+        .into();
+        return Ok(Response::new()
+            .add_message(refund_msg)
+            .add_attribute("ibc_transfer_refunded", offer_id)
+            .add_attribute("recovery_addr", recovery_addr));
+    }
+
+    // Otherwise keep the funds parked until someone retries the transfer
+    transfer.status = IbcTransferStatus::Failed {};
+    IBC_TRANSFERS.save(deps.storage, &offer_id, &transfer)?;
+
+    Ok(Response::new().add_attribute("ibc_transfer_failed", offer_id))
+}
+
+pub fn retry_ibc_transfer(
+    deps: DepsMut,
+    env: Env,
+    offer_id: String,
+) -> Result<Response, ContractError> {
+    let mut transfer = IBC_TRANSFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoIbcTransferFound {})?;
+
+    if transfer.status != (IbcTransferStatus::Failed {}) {
+        return Err(IbcTransferPending {});
+    }
+
+    // Anyone may retry since the funds can only go to the stored receiver
+    transfer.attempts += 1;
+    let sub_msg = send_ibc_transfer(deps.storage, &env, transfer)?;
+
+    Ok(Response::new()
+        .add_submessage(sub_msg)
+        .add_attribute("ibc_transfer_retried", offer_id))
+}
@@ -1,6 +1,28 @@
+pub mod actions;
+pub mod allowlist;
+pub mod approvals;
+pub mod breakers;
+pub mod callbacks;
+pub mod commitments;
 pub mod contract;
+pub mod contract_offers;
+pub mod counters;
+pub mod denoms;
 pub mod error;
 pub mod execute;
+pub mod fees;
+pub mod gates;
+pub mod governance;
+pub mod holdings;
+pub mod ibc;
+pub mod limits;
+pub mod maker_bonds;
+pub mod markets;
+pub mod migrate;
 pub mod msg;
+pub mod pause;
+pub mod payouts;
 pub mod query;
+pub mod reservations;
+pub mod staking;
 pub mod state;
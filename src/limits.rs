@@ -0,0 +1,164 @@
+use crate::approvals::refund_applicants;
+use crate::commitments::clear_commitments;
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    CreationCapReached, InaccurateFunds, InvalidMakerLimits, TooManyOpenOffers,
+};
+use crate::execute::assert_owner;
+use crate::maker_bonds::return_bond;
+use crate::migrate::assert_not_migrating;
+use crate::msg::{CapWindow, MakerLimits, Offer};
+use crate::reservations::release_reservation;
+use crate::state::{
+    CONFIG, IBC_TAKERS, MAKER_LIMITS, OFFERS, OFFERS_CREATED, OFFER_EXPIRIES, OPEN_OFFERS,
+};
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Timestamp,
+};
+
+// Counts a new offer against its maker's caps and checks the deposit sent with it.
+// Returns when the offer expires, if offers expire.
+pub fn open_offer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    maker: &Addr,
+    offer_id: &str,
+    funds: &[Coin],
+) -> Result<Option<Timestamp>, ContractError> {
+    let limits = MAKER_LIMITS.may_load(storage)?.unwrap_or_default();
+
+    match (&limits.deposit, funds) {
+        (None, []) => {}
+        (Some(deposit), [sent]) if sent == deposit => {}
+        _ => return Err(InaccurateFunds {}),
+    }
+
+    let open_offers = OPEN_OFFERS.may_load(storage, maker)?.unwrap_or_default();
+    if let Some(max_open_offers) = limits.max_open_offers {
+        if open_offers >= max_open_offers {
+            return Err(TooManyOpenOffers { max_open_offers });
+        }
+    }
+    OPEN_OFFERS.save(storage, maker, &(open_offers + 1))?;
+
+    if let Some(cap) = &limits.creation_cap {
+        let window = match cap.window {
+            CapWindow::Block => env.block.height,
+            CapWindow::Hour => env.block.time.seconds() / 3600,
+        };
+        let created = match OFFERS_CREATED.may_load(storage, maker)? {
+            Some((last_window, created)) if last_window == window => created,
+            _ => 0,
+        };
+        if created >= cap.max_offers {
+            return Err(CreationCapReached {
+                max_offers: cap.max_offers,
+            });
+        }
+        OFFERS_CREATED.save(storage, maker, &(window, created + 1))?;
+    }
+
+    let expires_at = limits.offer_ttl.map(|ttl| env.block.time.plus_seconds(ttl));
+    if let Some(expires_at) = expires_at {
+        OFFER_EXPIRIES.save(storage, (expires_at.seconds(), offer_id), &true)?;
+    }
+
+    Ok(expires_at)
+}
+
+// Removes an offer from the open offers and releases it from its maker's open offer count.
+// Returns the refunds of any applicants still waiting on the offer, its reservation deposit, the
+// maker's bond and the deposits of its commitments.
+pub fn close_offer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    offer_id: &str,
+    offer: &Offer,
+) -> StdResult<Vec<BankMsg>> {
+    OFFERS.remove(storage, offer_id);
+    IBC_TAKERS.remove(storage, offer_id);
+
+    let maker = Addr::unchecked(&offer.maker);
+    if let Some(open_offers) = OPEN_OFFERS.may_load(storage, &maker)? {
+        OPEN_OFFERS.save(storage, &maker, &open_offers.saturating_sub(1))?;
+    }
+    if let Some(expires_at) = offer.expires_at {
+        OFFER_EXPIRIES.remove(storage, (expires_at.seconds(), offer_id));
+    }
+
+    let mut msgs = refund_applicants(storage, offer_id)?;
+    msgs.extend(release_reservation(env, offer)?);
+    msgs.extend(return_bond(storage, offer_id, offer)?);
+    msgs.extend(clear_commitments(storage, env, offer_id, offer)?);
+    Ok(msgs)
+}
+
+// Pays out the anti-spam deposit of an offer, if it has one
+pub fn deposit_msg(offer: &Offer, to_address: &str) -> StdResult<Option<BankMsg>> {
+    offer
+        .deposit
+        .clone()
+        .map(|deposit| {
+            Ok(BankMsg::Send {
+                to_address: to_address.to_string(),
+                amount: vec![Coin::try_from(deposit)?],
+            })
+        })
+        .transpose()
+}
+
+pub fn update_maker_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    limits: MakerLimits,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let invalid_cap = limits
+        .creation_cap
+        .as_ref()
+        .is_some_and(|cap| cap.max_offers == 0);
+    let invalid_deposit = limits
+        .deposit
+        .as_ref()
+        .is_some_and(|deposit| deposit.amount.is_zero());
+    if invalid_cap || invalid_deposit || limits.offer_ttl == Some(0) {
+        return Err(InvalidMakerLimits {});
+    }
+
+    MAKER_LIMITS.save(deps.storage, &limits)?;
+
+    Ok(Response::new().add_attribute("action", "update_maker_limits"))
+}
+
+// Anyone can remove expired offers, whose deposits go to the fee recipient
+pub fn prune_expired_offers(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+    let now = env.block.time.seconds();
+    let expired = OFFER_EXPIRIES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take_while(|key| {
+            key.as_ref()
+                .map_or(true, |(expires_at, _)| *expires_at <= now)
+        })
+        .take(limit.unwrap_or(u32::MAX) as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let fee_recipient = CONFIG.load(deps.storage)?.fees.fee_recipient;
+    let mut response = Response::new().add_attribute("action", "prune_expired_offers");
+    for (_, offer_id) in &expired {
+        let offer = OFFERS.load(deps.storage, offer_id)?;
+        let refunds = close_offer(deps.storage, &env, offer_id, &offer)?;
+        response = response.add_messages(refunds);
+        if let Some(msg) = deposit_msg(&offer, fee_recipient.as_str())? {
+            response = response.add_message(msg);
+        }
+        response = response.add_attribute("pruned_offer", offer_id);
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,260 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    InaccurateFunds, InvalidMakerBond, InvalidMakerBondConfig, MakerBondRequired,
+};
+use crate::execute::{assert_owner, fulfill_offer_msg};
+use crate::limits::{close_offer, deposit_msg};
+use crate::msg::{MakerBondConfig, MakerBondEntry, MakerBondStatus, Offer};
+use crate::state::{
+    PendingSettlement, IBC_TAKERS, MAKER_BONDS, MAKER_BOND_CONFIG, OFFERS, PENDING_SETTLEMENT,
+};
+use cosmwasm_std::{
+    coin, Addr, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, Storage, SubMsg, SubMsgResult,
+};
+use osmosis_std::types::cosmos::authz::v1beta1::MsgExec;
+use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
+use osmosis_std::types::cosmos::base::v1beta1::Coin as Coin2;
+
+pub const MAKER_PULL_REPLY_ID: u64 = 5;
+
+fn ledger_key(offer_id: &str) -> StdResult<u64> {
+    offer_id
+        .parse()
+        .map_err(|_| StdError::generic_err(format!("invalid offer id {offer_id}")))
+}
+
+// Bonded offers pull the maker coin into the contract before settling, so only a failure of that
+// pull is down to the maker. IBC takers are refunded by ibc-hooks instead.
+pub fn maker_coin_prepulled(storage: &dyn Storage, offer_id: &str, offer: &Offer) -> bool {
+    offer.bond.is_some() && !IBC_TAKERS.has(storage, offer_id)
+}
+
+fn maker_pull_msg(env: &Env, offer: &Offer) -> CosmosMsg {
+    let exec_msg = MsgExec {
+        grantee: env.contract.address.to_string(),
+        msgs: vec![MsgSend {
+            from_address: offer.maker.clone(),
+            to_address: env.contract.address.to_string(),
+            amount: vec![offer.maker_coin.clone()],
+        }
+        .to_any()],
+    };
+    CosmosMsg::Stargate {
+        type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+        value: Binary::from(exec_msg),
+    }
+}
+
+// Checks the bond sent with a new offer and records it in the maker's ledger. Returns the rest
+// of the funds, which hold any anti-spam deposit.
+pub fn post_bond(
+    storage: &mut dyn Storage,
+    maker: &Addr,
+    offer_id: u64,
+    bond: Option<&Coin>,
+    funds: &[Coin],
+) -> Result<Vec<Coin>, ContractError> {
+    let config = MAKER_BOND_CONFIG.may_load(storage)?.unwrap_or_default();
+    let bond = match (bond, config.required_bond) {
+        (None, None) => return Ok(funds.to_vec()),
+        (None, Some(required)) => return Err(MakerBondRequired { bond: required }),
+        (Some(bond), Some(required))
+            if bond.denom != required.denom || bond.amount < required.amount =>
+        {
+            return Err(MakerBondRequired { bond: required })
+        }
+        (Some(bond), _) if bond.amount.is_zero() => {
+            return Err(InvalidMakerBond {
+                reason: "bond cannot be zero".to_string(),
+            })
+        }
+        (Some(bond), _) => bond,
+    };
+
+    // A deposit in the same denom arrives merged with the bond
+    let mut rest = vec![];
+    let mut bonded = false;
+    for sent in funds {
+        if sent.denom == bond.denom {
+            if sent.amount < bond.amount {
+                return Err(InaccurateFunds {});
+            }
+            bonded = true;
+            if sent.amount > bond.amount {
+                rest.push(coin((sent.amount - bond.amount).u128(), &sent.denom));
+            }
+        } else {
+            rest.push(sent.clone());
+        }
+    }
+    if !bonded {
+        return Err(InaccurateFunds {});
+    }
+
+    MAKER_BONDS.save(
+        storage,
+        (maker, offer_id),
+        &MakerBondEntry {
+            offer_id,
+            bond: bond.clone(),
+            status: MakerBondStatus::Held {},
+        },
+    )?;
+    Ok(rest)
+}
+
+// Returns the bond of a closing offer to its maker
+pub fn return_bond(
+    storage: &mut dyn Storage,
+    offer_id: &str,
+    offer: &Offer,
+) -> StdResult<Option<BankMsg>> {
+    let Some(bond) = offer.bond.clone() else {
+        return Ok(None);
+    };
+    let bond = Coin::try_from(bond)?;
+    MAKER_BONDS.save(
+        storage,
+        (&Addr::unchecked(&offer.maker), ledger_key(offer_id)?),
+        &MakerBondEntry {
+            offer_id: ledger_key(offer_id)?,
+            bond: bond.clone(),
+            status: MakerBondStatus::Returned {},
+        },
+    )?;
+    Ok(Some(BankMsg::Send {
+        to_address: offer.maker.clone(),
+        amount: vec![bond],
+    }))
+}
+
+// Settles an offer with the self-call. Bonded offers first pull the maker coin, and only settle
+// once the pull's reply succeeded.
+pub fn settlement_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    offer_id: &str,
+    offer: &Offer,
+    taker: &str,
+    funds: Vec<Coin2>,
+) -> Result<SubMsg, ContractError> {
+    if !maker_coin_prepulled(storage, offer_id, offer) {
+        return Ok(SubMsg::new(fulfill_offer_msg(env, offer_id, funds)));
+    }
+
+    PENDING_SETTLEMENT.save(
+        storage,
+        &PendingSettlement {
+            offer_id: offer_id.to_string(),
+            taker: taker.to_string(),
+            refund: funds
+                .into_iter()
+                .map(Coin::try_from)
+                .collect::<StdResult<_>>()?,
+        },
+    )?;
+    Ok(SubMsg::reply_always(
+        maker_pull_msg(env, offer),
+        MAKER_PULL_REPLY_ID,
+    ))
+}
+
+pub fn handle_maker_pull_reply(
+    deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_SETTLEMENT.load(deps.storage)?;
+    PENDING_SETTLEMENT.remove(deps.storage);
+
+    let error = match msg.result {
+        SubMsgResult::Ok(_) => {
+            let funds = pending.refund.into_iter().map(Coin2::from).collect();
+            return Ok(Response::new().add_message(fulfill_offer_msg(
+                &env,
+                &pending.offer_id,
+                funds,
+            )));
+        }
+        SubMsgResult::Err(error) => error,
+    };
+
+    // The pull only moves the maker coin from the maker to the contract, so it failed because the
+    // maker moved their funds or revoked the grant. The offer is closed, with part of the bond
+    // compensating the taker for what the contract holds of theirs.
+    let mut offer = OFFERS.load(deps.storage, &pending.offer_id)?;
+    let bond = Coin::try_from(offer.bond.take().ok_or(InvalidMakerBond {
+        reason: "settlement was replied to for an offer without a bond".to_string(),
+    })?)?;
+    let slash_bps = MAKER_BOND_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .slash_bps;
+    let compensation = coin(
+        bond.amount.multiply_ratio(slash_bps, 10_000u128).u128(),
+        &bond.denom,
+    );
+    let remaining = bond.amount - compensation.amount;
+
+    let offer_id = ledger_key(&pending.offer_id)?;
+    MAKER_BONDS.save(
+        deps.storage,
+        (&Addr::unchecked(&offer.maker), offer_id),
+        &MakerBondEntry {
+            offer_id,
+            bond: bond.clone(),
+            status: MakerBondStatus::Slashed {
+                compensation: compensation.clone(),
+            },
+        },
+    )?;
+    let refunds = close_offer(deps.storage, &env, &pending.offer_id, &offer)?;
+
+    let mut taker_payout = pending.refund;
+    taker_payout.push(compensation.clone());
+    taker_payout.retain(|payout| !payout.amount.is_zero());
+    let mut response = Response::new().add_messages(refunds);
+    if !taker_payout.is_empty() {
+        response = response.add_message(BankMsg::Send {
+            to_address: pending.taker.clone(),
+            amount: taker_payout,
+        });
+    }
+    if !remaining.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: offer.maker.clone(),
+            amount: vec![coin(remaining.u128(), &bond.denom)],
+        });
+    }
+    if let Some(refund) = deposit_msg(&offer, &offer.maker)? {
+        response = response.add_message(refund);
+    }
+
+    Ok(response
+        .add_attribute("settlement_failed", pending.offer_id)
+        .add_attribute("error", error)
+        .add_attribute("maker_bond_slashed", compensation.to_string())
+        .add_attribute("compensated_taker", pending.taker))
+}
+
+pub fn update_maker_bond_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: MakerBondConfig,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let zero_bond = config
+        .required_bond
+        .as_ref()
+        .is_some_and(|bond| bond.amount.is_zero());
+    if zero_bond || config.slash_bps > 10_000 {
+        return Err(InvalidMakerBondConfig {});
+    }
+    MAKER_BOND_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_maker_bond_config")
+        .add_attribute("slash_bps", config.slash_bps.to_string()))
+}
@@ -0,0 +1,172 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    BelowMinimumSize, DenomNotAllowed, IdenticalDenoms, InvalidDenom, InvalidLotSize,
+    InvalidMarket, InvalidTickSize, MarketDisabled, ZeroAmount,
+};
+use crate::execute::assert_owner;
+use crate::msg::{DenomListing, DenomPair, Market};
+use crate::state::{DENOM_LISTINGS, MARKETS, MARKETS_RESTRICTED};
+use cosmwasm_std::{Coin, DepsMut, MessageInfo, Response, Storage};
+
+// Checks an offer against the denom lists and the market of its pair
+pub fn validate_market(
+    storage: &dyn Storage,
+    maker_coin: &Coin,
+    taker_coin: &Coin,
+) -> Result<(), ContractError> {
+    if maker_coin.amount.is_zero() || taker_coin.amount.is_zero() {
+        return Err(ZeroAmount {});
+    }
+    match validate_market_denoms(storage, &maker_coin.denom, &taker_coin.denom)? {
+        Some(market) => check_offer_size(&market, maker_coin, taker_coin),
+        None => Ok(()),
+    }
+}
+
+// Checks both denoms are allowed and their market is open, returning the market if one is set.
+// Contract offers are checked by denom when made, as their amounts are only quoted on take.
+pub fn validate_market_denoms(
+    storage: &dyn Storage,
+    maker_denom: &str,
+    taker_denom: &str,
+) -> Result<Option<Market>, ContractError> {
+    if maker_denom == taker_denom {
+        return Err(IdenticalDenoms {});
+    }
+
+    let restricted = MARKETS_RESTRICTED.may_load(storage)?.unwrap_or_default();
+    for denom in [maker_denom, taker_denom] {
+        validate_denom(denom)?;
+        match DENOM_LISTINGS.may_load(storage, denom)? {
+            Some(DenomListing::Allowed) => {}
+            None if !restricted => {}
+            _ => {
+                return Err(DenomNotAllowed {
+                    denom: denom.to_string(),
+                })
+            }
+        }
+    }
+
+    match MARKETS.may_load(storage, (maker_denom, taker_denom))? {
+        Some(market) if market.enabled => Ok(Some(market)),
+        None if !restricted => Ok(None),
+        _ => Err(MarketDisabled {
+            maker_denom: maker_denom.to_string(),
+            taker_denom: taker_denom.to_string(),
+        }),
+    }
+}
+
+fn check_offer_size(
+    market: &Market,
+    maker_coin: &Coin,
+    taker_coin: &Coin,
+) -> Result<(), ContractError> {
+    if maker_coin.amount < market.min_maker_amount {
+        return Err(BelowMinimumSize {
+            min_maker_amount: market.min_maker_amount,
+        });
+    }
+    if !(maker_coin.amount % market.lot_size).is_zero() {
+        return Err(InvalidLotSize {
+            lot_size: market.lot_size,
+        });
+    }
+
+    // The price per lot has to be a whole number of ticks
+    let lots = maker_coin.amount / market.lot_size;
+    if !(taker_coin.amount % lots).is_zero()
+        || !((taker_coin.amount / lots) % market.tick_size).is_zero()
+    {
+        return Err(InvalidTickSize {
+            tick_size: market.tick_size,
+        });
+    }
+
+    Ok(())
+}
+
+// Same character set and length as the bank module accepts
+pub fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let valid = (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+
+    if !valid {
+        return Err(InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn update_denoms(
+    deps: DepsMut,
+    info: MessageInfo,
+    allow: Vec<String>,
+    block: Vec<String>,
+    unlist: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    for denom in &allow {
+        validate_denom(denom)?;
+        DENOM_LISTINGS.save(deps.storage, denom, &DenomListing::Allowed)?;
+    }
+    for denom in &block {
+        validate_denom(denom)?;
+        DENOM_LISTINGS.save(deps.storage, denom, &DenomListing::Blocked)?;
+    }
+    for denom in &unlist {
+        DENOM_LISTINGS.remove(deps.storage, denom);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_denoms")
+        .add_attribute("allowed", allow.join(","))
+        .add_attribute("blocked", block.join(","))
+        .add_attribute("unlisted", unlist.join(",")))
+}
+
+pub fn update_markets(
+    deps: DepsMut,
+    info: MessageInfo,
+    restricted: Option<bool>,
+    markets: Vec<Market>,
+    remove: Vec<DenomPair>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if let Some(restricted) = restricted {
+        MARKETS_RESTRICTED.save(deps.storage, &restricted)?;
+    }
+    for market in &markets {
+        validate_denom(&market.maker_denom)?;
+        validate_denom(&market.taker_denom)?;
+        if market.maker_denom == market.taker_denom
+            || market.lot_size.is_zero()
+            || market.tick_size.is_zero()
+        {
+            return Err(InvalidMarket {
+                maker_denom: market.maker_denom.clone(),
+                taker_denom: market.taker_denom.clone(),
+            });
+        }
+        MARKETS.save(
+            deps.storage,
+            (&market.maker_denom, &market.taker_denom),
+            market,
+        )?;
+    }
+    for pair in &remove {
+        MARKETS.remove(deps.storage, (&pair.maker_denom, &pair.taker_denom));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_markets")
+        .add_attribute("updated", markets.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
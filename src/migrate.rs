@@ -0,0 +1,245 @@
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    InvalidQueryLimit, MigrationDowngrade, MigrationInProgress, MigrationInvariant,
+    MissingMigrationConfig, NoMigrationInProgress, WrongContract,
+};
+use crate::fees::{save_pair_fees, validate_fee_bps};
+use crate::gates::validate_access_gate;
+use crate::msg::{InstantiateMsg, MigrateMsg, MigrationProgress, Offer};
+use crate::state::{
+    Config, FeeConfig, CONFIG, FULFILLED_OFFERS, MIGRATION, OFFERS, OFFER_ID_COUNTER,
+};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Order, Response, Storage};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
+use semver::Version;
+
+// Contracts deployed before cw2 was adopted never stored a version
+const V1_VERSION: &str = "0.1.0";
+
+// Stores re-encoded by the offer migration, in the order they are processed
+const OFFER_STORES: [Map<&str, Offer>; 2] = [OFFERS, FULFILLED_OFFERS];
+
+type MigrationStep = fn(DepsMut, &Env, &MigrateMsg) -> Result<(), ContractError>;
+
+// Ordered migration steps, each run when migrating from a version older than its own
+const MIGRATIONS: &[(&str, MigrationStep)] = &[("0.2.0", migrate_v0_2_0)];
+
+pub fn migrate_contract(
+    mut deps: DepsMut,
+    env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    let stored = match get_contract_version(deps.storage).ok() {
+        Some(stored) => {
+            if stored.contract != CONTRACT_NAME {
+                return Err(WrongContract {
+                    contract: stored.contract,
+                });
+            }
+            stored.version
+        }
+        None => V1_VERSION.to_string(),
+    };
+
+    let from = parse_version(&stored)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(MigrationDowngrade {
+            from: stored,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    for (version, step) in MIGRATIONS {
+        if from < parse_version(version)? {
+            step(deps.branch(), &env, &msg)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored)
+        .add_attribute("to_version", CONTRACT_VERSION);
+
+    // Large stores are re-encoded over several transactions, starting with this one
+    if MIGRATION.exists(deps.storage) {
+        res = res.add_attributes(migrate_offer_batch(deps.storage, msg.batch_size)?);
+    }
+
+    Ok(res)
+}
+
+// Continue a migration that did not fit into the migrate transaction
+pub fn continue_migration(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    if !MIGRATION.exists(deps.storage) {
+        return Err(NoMigrationInProgress {});
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "continue_migration")
+        .add_attribute("sender", info.sender)
+        .add_attributes(migrate_offer_batch(deps.storage, limit)?))
+}
+
+// v0.2.0 added the contract config and the fee and referral fields of `Offer`
+fn migrate_v0_2_0(deps: DepsMut, env: &Env, msg: &MigrateMsg) -> Result<(), ContractError> {
+    MIGRATION.save(
+        deps.storage,
+        &MigrationProgress {
+            store: 0,
+            last_key: None,
+            migrated: 0,
+        },
+    )?;
+
+    if !CONFIG.exists(deps.storage) {
+        let init = msg.config.clone().ok_or(MissingMigrationConfig {})?;
+        // v1 had no owner, so it defaults to the admin performing the migration
+        let admin = deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin;
+        save_v1_config(deps, init, admin)?;
+    }
+
+    Ok(())
+}
+
+fn save_v1_config(
+    deps: DepsMut,
+    init: InstantiateMsg,
+    admin: Option<String>,
+) -> Result<(), ContractError> {
+    validate_fee_bps(init.fee_bps)?;
+    validate_fee_bps(init.max_referral_fee_bps)?;
+    if init.max_query_limit == 0 {
+        return Err(InvalidQueryLimit {});
+    }
+    if let Some(gate) = &init.access_gate {
+        validate_access_gate(deps.api, gate)?;
+    }
+
+    let owner = init
+        .owner
+        .or(admin)
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner,
+            guardian: init
+                .guardian
+                .map(|guardian| deps.api.addr_validate(&guardian))
+                .transpose()?,
+            fees: FeeConfig {
+                fee_bps: init.fee_bps,
+                fee_recipient: deps.api.addr_validate(&init.fee_recipient)?,
+                fee_payer: init.fee_payer,
+                max_referral_fee_bps: init.max_referral_fee_bps,
+                referral_fee_source: init.referral_fee_source,
+            },
+            max_query_limit: init.max_query_limit,
+            access_gate: init.access_gate,
+        },
+    )?;
+    save_pair_fees(deps.storage, &init.pair_fees)?;
+
+    Ok(())
+}
+
+// Re-encode up to `limit` offers in the current layout, clearing the migration once all are done
+fn migrate_offer_batch(
+    storage: &mut dyn Storage,
+    limit: Option<u32>,
+) -> Result<Vec<(String, String)>, ContractError> {
+    let mut progress = MIGRATION.load(storage)?;
+    let mut remaining = limit.unwrap_or(u32::MAX) as usize;
+    let counter = OFFER_ID_COUNTER.may_load(storage)?.unwrap_or_default();
+
+    while remaining > 0 && (progress.store as usize) < OFFER_STORES.len() {
+        let store = &OFFER_STORES[progress.store as usize];
+        let start = progress.last_key.as_deref().map(Bound::exclusive);
+
+        // Offers written before v0.2.0 lack the optional fields, which decode as None
+        let batch = store
+            .range(storage, start, None, Order::Ascending)
+            .take(remaining)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (offer_id, offer) in &batch {
+            check_offer_id(offer_id, counter)?;
+            // Any offer that is both open and fulfilled is found while going through open ones
+            if progress.store == 0 && FULFILLED_OFFERS.has(storage, offer_id) {
+                return Err(MigrationInvariant {
+                    reason: format!("offer {offer_id} is both open and fulfilled"),
+                });
+            }
+            store.save(storage, offer_id, offer)?;
+        }
+
+        remaining -= batch.len();
+        progress.migrated += batch.len() as u64;
+        match batch.last() {
+            Some((offer_id, _)) if remaining == 0 => progress.last_key = Some(offer_id.clone()),
+            _ => {
+                progress.store += 1;
+                progress.last_key = None;
+            }
+        }
+    }
+
+    let done = progress.store as usize >= OFFER_STORES.len();
+    if done {
+        // Checked against the issued ids rather than a rescan, which the last batch couldn't fit
+        if progress.migrated > counter {
+            return Err(MigrationInvariant {
+                reason: format!(
+                    "migrated {} offers but only {counter} were issued",
+                    progress.migrated
+                ),
+            });
+        }
+        MIGRATION.remove(storage);
+    } else {
+        MIGRATION.save(storage, &progress)?;
+    }
+
+    Ok(vec![
+        ("migrated_offers".to_string(), progress.migrated.to_string()),
+        ("migration_complete".to_string(), done.to_string()),
+    ])
+}
+
+fn check_offer_id(offer_id: &str, counter: u64) -> Result<(), ContractError> {
+    match offer_id.parse::<u64>() {
+        Ok(id) if id > 0 && id <= counter => Ok(()),
+        _ => Err(MigrationInvariant {
+            reason: format!("offer id {offer_id} is outside the issued range 1..={counter}"),
+        }),
+    }
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|err| MigrationInvariant {
+        reason: format!("invalid version {version}: {err}"),
+    })
+}
+
+// Offers can't be made, taken or closed until all stored offers are in the current layout, since
+// the migration counts the offers it re-encodes
+pub fn assert_not_migrating(storage: &dyn Storage) -> Result<(), ContractError> {
+    if MIGRATION.exists(storage) {
+        return Err(MigrationInProgress {});
+    }
+    Ok(())
+}
@@ -1,14 +1,768 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Decimal, Event, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
 use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use std::fmt;
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    /// Defaults to the instantiating account
+    pub owner: Option<String>,
+    /// Account besides the owner allowed to pause operations
+    pub guardian: Option<String>,
+    /// Largest page size returned by paginated queries
+    pub max_query_limit: u32,
+    /// Contract every maker and taker has to be admitted by
+    pub access_gate: Option<AccessGate>,
+    /// Protocol fee in basis points, charged on every settlement
+    pub fee_bps: u16,
+    pub fee_recipient: String,
+    pub fee_payer: FeePayer,
+    /// Overrides of `fee_bps` for specific denom pairs
+    pub pair_fees: Vec<PairFee>,
+    /// Token bonded to earn protocol fees. Without it, fees go to `fee_recipient`.
+    pub staking_token: Option<StakingToken>,
+    /// Highest referral fee a maker or taker may request for their referrer
+    pub max_referral_fee_bps: u16,
+    pub referral_fee_source: ReferralFeeSource,
+}
+
+/// Where referral fees are taken from. Either way they are paid by the fee paying side.
+#[cw_serde]
+pub enum ReferralFeeSource {
+    /// Carved out of the protocol fee, so the referral cut never exceeds it
+    ProtocolFee,
+    /// Charged on top of the protocol fee
+    Proceeds,
+}
+
+/// Front end or wallet that referred a maker or taker, and the fee it requested
+#[cw_serde]
+pub struct Referral {
+    pub address: String,
+    pub fee_bps: u16,
+}
+
+#[cw_serde]
+pub enum StakingToken {
+    Native { denom: String },
+    Cw20 { contract_addr: String },
+}
+
+/// Side of the swap whose proceeds the protocol fee is deducted from
+#[cw_serde]
+pub enum FeePayer {
+    Maker,
+    Taker,
+}
+
+#[cw_serde]
+pub struct PairFee {
+    pub maker_denom: String,
+    pub taker_denom: String,
+    pub fee_bps: u16,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    MakeOffer { maker_coin: Coin, taker_coin: Coin },
-    OfferTaker { offer_id: String },
-    FulfillOffer { offer_id: String },
+    MakeOffer {
+        maker_coin: Coin,
+        taker_coin: Coin,
+        referral: Option<Referral>,
+        /// Contract takers of this offer have to be admitted by, on top of any global gate
+        access_gate: Option<AccessGate>,
+        /// Merkle root of the addresses allowed to take this offer
+        taker_allowlist: Option<TakerAllowlist>,
+        /// Tokens takers have to hold, checked when the offer is taken
+        holding_requirements: Option<Vec<HoldingRequirement>>,
+        /// Receives the taker coin instead of the maker
+        proceeds_recipient: Option<String>,
+        /// Splits the taker coin between several recipients by weight instead
+        payout_split: Option<Vec<Payout>>,
+        /// Put the taker coin to use once the offer settles, refunding the maker if it fails
+        proceeds_action: Option<ProceedsAction>,
+        /// Seconds the maker has to approve an applicant. Takers of such offers apply with
+        /// `OfferTaker`, escrowing the taker coin, instead of settling.
+        approval_window: Option<u64>,
+        /// Blocks a taker has to wait between `CommitTake` and `RevealTake`. Such offers can
+        /// only be taken by reveal.
+        reveal_delay: Option<u64>,
+        /// Sent alongside any deposit. Part of it compensates the taker if the maker coin can't
+        /// be pulled from the maker at settlement.
+        bond: Option<cosmwasm_std::Coin>,
+    },
+    OfferTaker {
+        offer_id: String,
+        referral: Option<Referral>,
+        /// Required to take offers with a taker allowlist
+        allowlist_proof: Option<AllowlistProof>,
+        /// Receives the maker coin instead of the taker
+        recipient: Option<String>,
+        /// Delivered to the taker contract as `SwapCallback` once it holds the maker coin
+        callback: Option<TakerCallback>,
+    },
+    /// Commit to taking an offer ahead of revealing the take. `commitment` is the hex encoded
+    /// sha256 hash of `"{offer_id}:{taker}:{salt}"`.
+    CommitTake {
+        offer_id: String,
+        commitment: String,
+    },
+    /// Take an offer committed to in an earlier block. Settles like `OfferTaker` if no earlier
+    /// commitment to the offer could still be revealed.
+    RevealTake {
+        offer_id: String,
+        salt: String,
+        referral: Option<Referral>,
+        allowlist_proof: Option<AllowlistProof>,
+        recipient: Option<String>,
+        callback: Option<TakerCallback>,
+    },
+    /// Lock an offer so only the sender can take it for the next `blocks` blocks, posting the
+    /// reservation deposit. The deposit is returned once the sender takes the offer and
+    /// forfeited to the maker if the reservation lapses.
+    ReserveOffer {
+        offer_id: String,
+        blocks: u64,
+    },
+    /// Pays the taker coin of a flash take from within its `SwapCallback`
+    RepayFlash {
+        offer_id: String,
+    },
+    /// Take an offer from another chain. Sent as the wasm memo of an ICS20 transfer routed
+    /// through Osmosis ibc-hooks, the maker coin is sent back to `receiver` over `channel`.
+    IbcOfferTaker {
+        offer_id: String,
+        channel: String,
+        receiver: String,
+        recovery_addr: Option<String>,
+    },
+    /// Resend a return transfer that failed after all automatic retries were used up
+    RetryIbcTransfer {
+        offer_id: String,
+    },
+    FulfillOffer {
+        offer_id: String,
+    },
+    /// Maker only. Withdraws an offer that has not been taken.
+    CancelOffer {
+        offer_id: String,
+    },
+    /// Maker only. Settles an approval offer with one of its applicants.
+    ApproveApplicant {
+        offer_id: String,
+        applicant: String,
+    },
+    /// Maker only. Refunds an applicant of an approval offer.
+    RejectApplicant {
+        offer_id: String,
+        applicant: String,
+    },
+    /// Refunds the applicants of an offer whose approval window has lapsed
+    RefundLapsedApplicants {
+        offer_id: String,
+    },
+    /// Propose different terms for an offer, escrowing the `taker_coin` sent
+    Counter {
+        offer_id: String,
+        taker_coin: Coin,
+    },
+    /// Maker only. Settles the offer with the counter's proposer on the counter's terms.
+    AcceptCounter {
+        counter_id: u64,
+    },
+    /// Maker only. Refunds the counter to its proposer.
+    RejectCounter {
+        counter_id: u64,
+    },
+    /// Proposer only. Withdraws a counter, refunding its escrow.
+    WithdrawCounter {
+        counter_id: u64,
+    },
+    /// Offer sent by a maker contract that quotes its price when the offer is taken. Escrowed
+    /// offers are paid out of the native coin sent with the message.
+    MakeContractOffer {
+        maker_asset: MakerAsset,
+        taker_denom: String,
+        settlement: FillSettlement,
+    },
+    /// Take a contract offer with the taker coin sent, failing if the maker's quote pays less
+    /// than `min_maker_amount`
+    TakeContractOffer {
+        offer_id: String,
+        min_maker_amount: Uint128,
+        referral: Option<Referral>,
+    },
+    /// Maker only. Withdraws a contract offer, refunding what is left in escrow.
+    CancelContractOffer {
+        offer_id: String,
+    },
+    /// Owner only. `pair_fees` replaces the full set of pair overrides when provided.
+    UpdateFees {
+        fee_bps: Option<u16>,
+        fee_recipient: Option<String>,
+        fee_payer: Option<FeePayer>,
+        pair_fees: Option<Vec<PairFee>>,
+        max_referral_fee_bps: Option<u16>,
+        referral_fee_source: Option<ReferralFeeSource>,
+    },
+    /// Bond the native staking token sent with the message
+    Bond {},
+    /// Bond a CW20 staking token through `Cw20ExecuteMsg::Send` with a `ReceiveMsg::Bond` hook
+    Receive(Cw20ReceiveMsg),
+    Unbond {
+        amount: Uint128,
+    },
+    ClaimRewards {},
+    /// Owner only
+    UpdateConfig {
+        max_query_limit: Option<u32>,
+    },
+    /// Owner only. The proposed owner has to accept before ownership moves.
+    ProposeNewOwner {
+        owner: String,
+    },
+    AcceptOwnership {},
+    /// Owner only. Leaves the contract without an owner for good.
+    RenounceOwnership {},
+    /// Owner only. Adds denoms to the allowlist or blocklist, or removes them from either.
+    UpdateDenoms {
+        allow: Vec<String>,
+        block: Vec<String>,
+        unlist: Vec<String>,
+    },
+    /// Owner only. Adds or replaces markets and removes others. While `restricted`, only
+    /// allowlisted denoms in enabled markets can trade.
+    UpdateMarkets {
+        restricted: Option<bool>,
+        markets: Vec<Market>,
+        remove: Vec<DenomPair>,
+    },
+    /// Owner only. While `require_approved`, IBC denoms can only trade if they arrived over an
+    /// approved channel.
+    UpdateIbcChannels {
+        require_approved: Option<bool>,
+        approve: Vec<String>,
+        revoke: Vec<String>,
+    },
+    /// Owner only. Approves or revokes the contracts makers can have their proceeds sent to
+    UpdateActionContracts {
+        approve: Vec<String>,
+        revoke: Vec<String>,
+    },
+    /// Owner only. Sets the deposit and longest window of reservations, disabling them if unset.
+    UpdateReservationConfig {
+        config: Option<ReservationConfig>,
+    },
+    /// Owner only. Sets the deposit posted with commitments, disabling commit-reveal taking if
+    /// unset.
+    UpdateCommitmentConfig {
+        config: Option<CommitmentConfig>,
+    },
+    /// Owner only. Sets the bond makers have to post and the share of it slashed when settlement
+    /// can't pull the maker coin.
+    UpdateMakerBondConfig {
+        config: MakerBondConfig,
+    },
+    /// Owner only. Replaces the maker limits, which only apply to offers made afterwards.
+    UpdateMakerLimits {
+        limits: MakerLimits,
+    },
+    /// Owner only. Adds or replaces volume limits and removes others. A denom whose settled volume
+    /// exceeds its limit stops settling until its breaker is reset.
+    UpdateVolumeLimits {
+        limits: Vec<VolumeLimit>,
+        remove: Vec<String>,
+    },
+    /// Owner only. Lets a denom whose breaker tripped settle again.
+    ResetBreaker {
+        denom: String,
+    },
+    /// Remove up to `limit` expired offers, forfeiting their deposits to the fee recipient
+    PruneExpiredOffers {
+        limit: Option<u32>,
+    },
+    /// Owner only. Sets the contract every maker and taker has to be admitted by, removing it
+    /// when unset.
+    UpdateAccessGate {
+        access_gate: Option<AccessGate>,
+    },
+    /// Owner only. Replaces the guardian, removing it when unset.
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
+    /// Pause or unpause an operation, for a single denom or all of them when `denom` is unset.
+    /// The owner and guardian can pause, only the owner can unpause.
+    SetPause {
+        operation: Operation,
+        denom: Option<String>,
+        paused: bool,
+    },
+    /// Re-encode the next `limit` offers of a migration that did not finish in one transaction
+    ContinueMigration {
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub enum DenomListing {
+    Allowed,
+    Blocked,
+}
+
+/// Trading rules for offers selling `maker_denom` for `taker_denom`
+#[cw_serde]
+pub struct Market {
+    pub maker_denom: String,
+    pub taker_denom: String,
+    pub enabled: bool,
+    /// Smallest maker coin amount an offer may have
+    pub min_maker_amount: Uint128,
+    /// Maker coin amounts have to be a multiple of the lot size
+    pub lot_size: Uint128,
+    /// Price step, in taker denom units per lot
+    pub tick_size: Uint128,
+}
+
+#[cw_serde]
+pub struct DenomPair {
+    pub maker_denom: String,
+    pub taker_denom: String,
+}
+
+/// Limits on how many offers makers can have and create, none by default
+#[cw_serde]
+#[derive(Default)]
+pub struct MakerLimits {
+    /// Open offers a maker may have at once
+    pub max_open_offers: Option<u32>,
+    /// Seconds an offer has to stay open before its maker can cancel it
+    pub min_offer_lifetime: u64,
+    pub creation_cap: Option<CreationCap>,
+    /// Sent with `MakeOffer`, refunded on fill or cancel and forfeited when the offer expires
+    pub deposit: Option<cosmwasm_std::Coin>,
+    /// Seconds until an untaken offer expires and can be pruned
+    pub offer_ttl: Option<u64>,
+}
+
+#[cw_serde]
+pub struct MakerBondConfig {
+    /// Least bond every offer has to be made with, if any
+    pub required_bond: Option<cosmwasm_std::Coin>,
+    /// Share of a bond paid to the taker when the maker coin can't be pulled
+    pub slash_bps: u16,
+}
+
+impl Default for MakerBondConfig {
+    fn default() -> Self {
+        MakerBondConfig {
+            required_bond: None,
+            slash_bps: 5_000,
+        }
+    }
+}
+
+#[cw_serde]
+pub enum MakerBondStatus {
+    Held {},
+    Returned {},
+    /// Part of the bond was paid to the taker and the rest returned
+    Slashed {
+        compensation: cosmwasm_std::Coin,
+    },
+}
+
+#[cw_serde]
+pub struct MakerBondEntry {
+    pub offer_id: u64,
+    pub bond: cosmwasm_std::Coin,
+    pub status: MakerBondStatus,
+}
+
+#[cw_serde]
+pub struct ReservationConfig {
+    /// Posted with `ReserveOffer`
+    pub deposit: cosmwasm_std::Coin,
+    /// Most blocks an offer can be reserved for
+    pub max_blocks: u64,
+}
+
+#[cw_serde]
+pub struct CommitmentConfig {
+    /// Posted with `CommitTake`, returned on reveal and forfeited to the maker if the commitment
+    /// expires
+    pub deposit: cosmwasm_std::Coin,
+}
+
+/// Lock on an offer held by a taker until `expires_at_height`
+#[cw_serde]
+pub struct Reservation {
+    pub reserver: String,
+    pub deposit: Coin,
+    pub expires_at_height: u64,
+}
+
+/// Offers a maker may create per block or per hour
+#[cw_serde]
+pub struct CreationCap {
+    pub max_offers: u32,
+    pub window: CapWindow,
+}
+
+#[cw_serde]
+pub enum CapWindow {
+    Block,
+    Hour,
+}
+
+/// Contract that decides who may make or take offers
+#[cw_serde]
+pub enum AccessGate {
+    /// Members of a cw4 group are admitted
+    Cw4Group { contract: String },
+    /// Admitted when the contract answers `GatekeeperQueryMsg::IsAuthorized` with true
+    Gatekeeper { contract: String },
+}
+
+impl AccessGate {
+    pub fn contract(&self) -> &str {
+        match self {
+            AccessGate::Cw4Group { contract } | AccessGate::Gatekeeper { contract } => contract,
+        }
+    }
+}
+
+/// Taker waiting for the maker of an approval offer, with the taker coin in escrow
+#[cw_serde]
+pub struct Application {
+    pub applicant: String,
+    pub taker_coin: Coin,
+    pub applied_at: Timestamp,
+    /// Approving fails after this, and the application can be refunded
+    pub lapses_at: Timestamp,
+    pub referral: Option<Referral>,
+    pub recipient: Option<String>,
+}
+
+/// Terms a taker proposes instead of an offer's, backed by the escrowed `taker_coin`
+#[cw_serde]
+pub struct Counter {
+    pub counter_id: u64,
+    pub offer_id: String,
+    pub proposer: String,
+    pub taker_coin: Coin,
+    pub created_at: Timestamp,
+    /// Accepting fails after this, but the proposer can still withdraw
+    pub expires_at: Timestamp,
+}
+
+/// Asset sold by a maker contract
+#[cw_serde]
+pub enum MakerAsset {
+    Native { denom: String },
+    Cw20 { contract: String },
+}
+
+impl MakerAsset {
+    /// Denom, or contract address of a cw20, used for pauses, listings and fees
+    pub fn denom(&self) -> &str {
+        match self {
+            MakerAsset::Native { denom } => denom,
+            MakerAsset::Cw20 { contract } => contract,
+        }
+    }
+}
+
+/// How the maker asset reaches the taker of a contract offer
+#[cw_serde]
+pub enum FillSettlement {
+    /// Paid out of the native coin the maker escrowed with the offer
+    Escrow {},
+    /// Transferred to the taker by the maker contract when it confirms the fill
+    MakerTransfer {},
+}
+
+#[cw_serde]
+pub struct ContractOffer {
+    pub maker: String,
+    pub maker_asset: MakerAsset,
+    pub taker_denom: String,
+    pub settlement: FillSettlement,
+    /// Left in escrow, always zero for `MakerTransfer` offers
+    pub escrow: Uint128,
+    pub created_at: Timestamp,
+}
+
+/// Query maker contracts have to implement to make contract offers
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum MakerQueryMsg {
+    #[returns(Quote)]
+    QuoteRequest {
+        offer_id: String,
+        taker: String,
+        taker_coin: cosmwasm_std::Coin,
+    },
+}
+
+/// Price of one unit of the taker coin in units of the maker asset, and the most of the maker
+/// asset the maker sells in one fill
+#[cw_serde]
+pub struct Quote {
+    pub price: Decimal,
+    pub max_maker_amount: Uint128,
+}
+
+/// Message maker contracts have to handle to make contract offers. It carries the taker coin
+/// less fees, and `MakerTransfer` makers have to send `maker_amount` to the taker while handling it.
+#[cw_serde]
+pub enum MakerExecuteMsg {
+    ConfirmFill {
+        offer_id: String,
+        taker: String,
+        maker_amount: Uint128,
+        taker_coin: cosmwasm_std::Coin,
+    },
+}
+
+/// Query gatekeeper contracts have to implement
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum GatekeeperQueryMsg {
+    #[returns(IsAuthorizedResponse)]
+    IsAuthorized {
+        participant: String,
+        operation: Operation,
+    },
+}
+
+#[cw_serde]
+pub struct IsAuthorizedResponse {
+    pub authorized: bool,
+}
+
+/// Addresses allowed to take an offer, as the hex encoded root of a SHA-256 Merkle tree. Leaves
+/// are the hash of the address, or of the address followed by its cap with `per_address_caps`.
+/// Pairs are hashed in sorted order.
+#[cw_serde]
+pub struct TakerAllowlist {
+    pub merkle_root: String,
+    pub per_address_caps: bool,
+}
+
+/// Proof that the taker is a leaf of an offer's allowlist
+#[cw_serde]
+pub struct AllowlistProof {
+    /// Hex encoded sibling hashes from the leaf up to the root
+    pub proof: Vec<String>,
+    /// Most of the maker coin the taker may take across offers with the same allowlist
+    pub cap: Option<Uint128>,
+}
+
+/// Token a taker has to hold to take an offer
+#[cw_serde]
+pub enum HoldingRequirement {
+    NativeBalance {
+        denom: String,
+        min_amount: Uint128,
+    },
+    Cw20Balance {
+        contract: String,
+        min_amount: Uint128,
+    },
+    /// Any token of the collection
+    Cw721Owner {
+        contract: String,
+    },
+}
+
+impl fmt::Display for HoldingRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HoldingRequirement::NativeBalance { denom, min_amount } => {
+                write!(f, "at least {min_amount}{denom}")
+            }
+            HoldingRequirement::Cw20Balance {
+                contract,
+                min_amount,
+            } => write!(f, "at least {min_amount} of cw20 {contract}"),
+            HoldingRequirement::Cw721Owner { contract } => {
+                write!(f, "a token of cw721 {contract}")
+            }
+        }
+    }
+}
+
+/// Callback delivered to a taker contract after it receives the maker coin. In flash mode the
+/// taker sends no funds with `OfferTaker` and has to pay the taker coin with `RepayFlash` during
+/// the callback, or the whole take reverts.
+#[cw_serde]
+pub struct TakerCallback {
+    pub msg: Binary,
+    pub flash: bool,
+}
+
+/// Message taker contracts have to handle to take offers with a callback
+#[cw_serde]
+pub enum TakerCallbackMsg {
+    SwapCallback {
+        offer_id: String,
+        /// Sent to the taker before the callback
+        maker_coin: cosmwasm_std::Coin,
+        /// Owed by the taker during the callback in flash mode, already paid otherwise
+        taker_coin: cosmwasm_std::Coin,
+        flash: bool,
+        msg: Binary,
+    },
+}
+
+/// What settlement does with the maker's share of the taker coin instead of sending it to them
+#[cw_serde]
+pub enum ProceedsAction {
+    /// Executes an approved contract with the proceeds attached. `{{offer_id}}`, `{{maker}}`,
+    /// `{{amount}}` and `{{denom}}` in the JSON `msg` are replaced with their values.
+    WasmExecute { contract: String, msg: String },
+    /// Sends the proceeds to `receiver` over an ICS20 channel
+    IbcTransfer { channel: String, receiver: String },
+    /// Delegates the proceeds from the maker's account, which needs an authz grant for
+    /// `MsgDelegate` to this contract
+    Delegate { validator: String },
+}
+
+impl ProceedsAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProceedsAction::WasmExecute { .. } => "wasm_execute",
+            ProceedsAction::IbcTransfer { .. } => "ibc_transfer",
+            ProceedsAction::Delegate { .. } => "delegate",
+        }
+    }
+}
+
+/// Recipient of a weighted share of the taker coin
+#[cw_serde]
+pub struct Payout {
+    pub address: String,
+    pub weight: u32,
+}
+
+/// Most of a denom that can settle within a rolling window before its circuit breaker trips
+#[cw_serde]
+pub struct VolumeLimit {
+    pub denom: String,
+    pub max_volume: Uint128,
+    /// Length of the window in seconds
+    pub window: u64,
+}
+
+#[cw_serde]
+pub struct BreakerStatus {
+    pub limit: VolumeLimit,
+    /// Volume settled within the current window
+    pub window_volume: Uint128,
+    /// Set once the breaker trips, until the owner resets it
+    pub tripped_at: Option<Timestamp>,
+}
+
+/// Where a denom comes from and how to display it
+#[cw_serde]
+pub struct DenomInfo {
+    pub denom: String,
+    pub base_denom: String,
+    /// IBC path the denom travelled, empty for native denoms
+    pub path: String,
+    /// None when the denom has no bank metadata
+    pub display: Option<String>,
+    pub decimals: Option<u32>,
+}
+
+/// Operations that can be paused independently
+#[cw_serde]
+pub enum Operation {
+    Make,
+    Take,
+    Settle,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Make => "make",
+            Operation::Take => "take",
+            Operation::Settle => "settle",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Contract config to create when migrating from v1, which had none.
+    /// The owner defaults to the contract admin.
+    pub config: Option<InstantiateMsg>,
+    /// Number of offers re-encoded in the migrate transaction, all of them when unset.
+    /// The rest are re-encoded with `ContinueMigration`.
+    pub batch_size: Option<u32>,
+}
+
+#[cw_serde]
+pub struct MigrationProgress {
+    /// Index of the offer store being re-encoded, open offers first and fulfilled offers second
+    pub store: u8,
+    pub last_key: Option<String>,
+    pub migrated: u64,
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    Bond {},
+}
+
+#[cw_serde]
+pub enum SudoMsg {
+    #[serde(rename = "ibc_lifecycle_complete")]
+    IBCLifecycleComplete(IBCLifecycleComplete),
+    /// Governance only. Removes an open offer regardless of its maker.
+    ForceCancelOffer { offer_id: String },
+    /// Governance only. Stops offers in `denom` from being made or taken.
+    DelistDenom { denom: String },
+    /// Governance only. Lifts a delisting.
+    RelistDenom { denom: String },
+    /// Governance only. Same as `ExecuteMsg::SetPause`, without the owner and guardian checks.
+    SetPause {
+        operation: Operation,
+        denom: Option<String>,
+        paused: bool,
+    },
+    /// Governance only. Replaces the owner immediately, dropping any pending owner.
+    ReplaceOwner { owner: String },
+}
+
+/// A governance action taken through sudo, in the order it was recorded
+#[cw_serde]
+pub struct AuditEntry {
+    pub id: u64,
+    pub height: u64,
+    pub time: Timestamp,
+    /// Event the action emitted
+    pub event: Event,
+}
+
+#[cw_serde]
+pub enum IBCLifecycleComplete {
+    #[serde(rename = "ibc_ack")]
+    IBCAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    #[serde(rename = "ibc_timeout")]
+    IBCTimeout { channel: String, sequence: u64 },
 }
 
 #[cw_serde]
@@ -17,6 +771,68 @@ pub struct Offer {
     pub taker: Option<String>,
     pub maker_coin: Coin,
     pub taker_coin: Coin,
+    /// Protocol fee charged when the offer was fulfilled
+    pub fee: Option<Coin>,
+    pub maker_referral: Option<Referral>,
+    pub taker_referral: Option<Referral>,
+    pub created_at: Option<Timestamp>,
+    /// Offers can't be taken once expired
+    pub expires_at: Option<Timestamp>,
+    /// Anti-spam deposit held until the offer is filled, cancelled or pruned
+    pub deposit: Option<Coin>,
+    /// Contract takers have to be admitted by
+    pub access_gate: Option<AccessGate>,
+    pub taker_allowlist: Option<TakerAllowlist>,
+    pub holding_requirements: Option<Vec<HoldingRequirement>>,
+    /// Receives the taker coin instead of the maker
+    pub proceeds_recipient: Option<String>,
+    /// Receives the maker coin instead of the taker
+    pub taker_recipient: Option<String>,
+    pub payout_split: Option<Vec<Payout>>,
+    pub taker_callback: Option<TakerCallback>,
+    pub proceeds_action: Option<ProceedsAction>,
+    pub approval_window: Option<u64>,
+    /// Only the reserver can take the offer until the reservation lapses
+    pub reservation: Option<Reservation>,
+    pub reveal_delay: Option<u64>,
+    /// Returned when the offer closes, unless slashed because the maker couldn't be pulled from
+    pub bond: Option<Coin>,
+}
+
+/// Intent to take an offer, revealed with `RevealTake`
+#[cw_serde]
+pub struct Commitment {
+    pub offer_id: String,
+    pub committer: String,
+    /// Position among all commitments, earlier commitments to the offer take precedence
+    pub sequence: u64,
+    pub committed_at: Timestamp,
+    pub committed_at_height: u64,
+    /// Last block the commitment can be revealed in, and holds back later ones, is the one before
+    pub expires_at_height: u64,
+    pub deposit: Coin,
+}
+
+#[cw_serde]
+pub struct IbcReceiver {
+    pub channel: String,
+    pub receiver: String,
+    pub recovery_addr: Option<String>,
+}
+
+#[cw_serde]
+pub enum IbcTransferStatus {
+    Pending { sequence: u64 },
+    Failed {},
+}
+
+#[cw_serde]
+pub struct IbcTransfer {
+    pub offer_id: String,
+    pub receiver: IbcReceiver,
+    pub coin: Coin,
+    pub attempts: u32,
+    pub status: IbcTransferStatus,
 }
 
 #[cw_serde]
@@ -29,4 +845,181 @@ pub enum QueryMsg {
     },
     #[returns(Offer)]
     FulfilledOffers { offer_id: u64 },
+    #[returns(Option<IbcTransfer>)]
+    IbcTransfer { offer_id: String },
+    #[returns(FeesResponse)]
+    Fees {},
+    #[returns(StakingStateResponse)]
+    StakingState {},
+    #[returns(StakerResponse)]
+    Staker { address: String },
+    /// Unclaimed fee rewards of a staker, one coin per fee denom
+    #[returns(Vec<Coin>)]
+    PendingRewards { staker: String },
+    #[returns(ReferrerStatsResponse)]
+    ReferrerStats { referrer: String },
+    #[returns(ConfigResponse)]
+    Config {},
+    /// cw2 name and version of the deployed code
+    #[returns(cw2::ContractVersion)]
+    ContractInfo {},
+    #[returns(Option<MigrationProgress>)]
+    MigrationProgress {},
+    #[returns(Vec<Pause>)]
+    Pauses {},
+    #[returns(Vec<String>)]
+    DelistedDenoms {},
+    #[returns(MarketsResponse)]
+    Markets {},
+    /// Open offers with the trace and display metadata of both denoms
+    #[returns(Vec<OfferWithDenoms>)]
+    OffersWithDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(IbcChannelsResponse)]
+    IbcChannels {},
+    /// Pending applicants of an approval offer
+    #[returns(Vec<Application>)]
+    Applicants { offer_id: String },
+    /// Counters proposed for an offer, oldest first
+    #[returns(Vec<Counter>)]
+    CountersByOffer {
+        offer_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Counters an address has proposed and not yet withdrawn, oldest first
+    #[returns(Vec<Counter>)]
+    CountersByProposer {
+        proposer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<ContractOffer>)]
+    ContractOffer { offer_id: String },
+    /// Maker asset amount the contract offer's maker currently quotes for `taker_coin`
+    #[returns(Uint128)]
+    ContractOfferQuote {
+        offer_id: String,
+        taker: String,
+        taker_coin: cosmwasm_std::Coin,
+    },
+    /// Contracts approved for `WasmExecute` proceeds actions
+    #[returns(Vec<String>)]
+    ActionContracts {},
+    #[returns(MakerLimits)]
+    MakerLimits {},
+    #[returns(Option<ReservationConfig>)]
+    ReservationConfig {},
+    #[returns(MakerBondConfig)]
+    MakerBondConfig {},
+    /// Bonds a maker has posted with their offers, oldest first
+    #[returns(Vec<MakerBondEntry>)]
+    MakerBonds {
+        maker: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<Commitment>)]
+    Commitment { commitment: String },
+    #[returns(Option<CommitmentConfig>)]
+    CommitmentConfig {},
+    /// Number of open offers a maker has
+    #[returns(u32)]
+    MakerOpenOffers { maker: String },
+    /// Volume limits with the current window usage and whether their breaker tripped
+    #[returns(Vec<BreakerStatus>)]
+    Breakers {},
+    /// Maker coin amount an address has taken from offers with the given allowlist root
+    #[returns(Uint128)]
+    AllowlistTaken {
+        merkle_root: String,
+        address: String,
+    },
+    #[returns(Vec<AuditEntry>)]
+    AuditLog {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct OfferWithDenoms {
+    pub offer_id: String,
+    pub offer: Offer,
+    pub maker_denom: DenomInfo,
+    pub taker_denom: DenomInfo,
+}
+
+#[cw_serde]
+pub struct IbcChannelsResponse {
+    pub require_approved: bool,
+    pub approved: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MarketsResponse {
+    pub restricted: bool,
+    pub allowed_denoms: Vec<String>,
+    pub blocked_denoms: Vec<String>,
+    pub markets: Vec<Market>,
+}
+
+#[cw_serde]
+pub struct Pause {
+    pub operation: Operation,
+    /// None when the operation is paused for every denom
+    pub denom: Option<String>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Option<String>,
+    pub guardian: Option<String>,
+    pub pending_owner: Option<String>,
+    pub fee_bps: u16,
+    pub fee_recipient: String,
+    pub fee_payer: FeePayer,
+    pub max_referral_fee_bps: u16,
+    pub referral_fee_source: ReferralFeeSource,
+    pub max_query_limit: u32,
+    pub access_gate: Option<AccessGate>,
+}
+
+#[cw_serde]
+pub struct FeesResponse {
+    pub fee_bps: u16,
+    pub fee_recipient: String,
+    pub fee_payer: FeePayer,
+    pub pair_fees: Vec<PairFee>,
+    pub max_referral_fee_bps: u16,
+    pub referral_fee_source: ReferralFeeSource,
+}
+
+#[cw_serde]
+pub struct RewardIndex {
+    pub denom: String,
+    pub index: Decimal,
+}
+
+#[cw_serde]
+pub struct StakingStateResponse {
+    pub staking_token: Option<StakingToken>,
+    pub total_bonded: Uint128,
+    pub reward_indexes: Vec<RewardIndex>,
+}
+
+#[cw_serde]
+pub struct StakerResponse {
+    pub address: String,
+    pub bonded: Uint128,
+}
+
+#[cw_serde]
+pub struct ReferrerStatsResponse {
+    pub referrer: String,
+    pub referred_trades: u64,
+    /// Total referral fees earned, one coin per denom
+    pub earned: Vec<Coin>,
 }
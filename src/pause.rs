@@ -0,0 +1,64 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{NotOwner, NotOwnerOrGuardian, Paused};
+use crate::msg::Operation;
+use crate::state::{CONFIG, PAUSED};
+use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult, Storage};
+
+// Fails if the operation is paused globally or for any of the given denoms
+pub fn assert_not_paused(
+    storage: &dyn Storage,
+    operation: Operation,
+    denoms: &[&str],
+) -> Result<(), ContractError> {
+    let key = operation.as_str();
+    let paused = PAUSED.has(storage, (key, ""))
+        || denoms.iter().any(|denom| PAUSED.has(storage, (key, denom)));
+
+    if paused {
+        return Err(Paused { operation });
+    }
+    Ok(())
+}
+
+pub fn set_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    operation: Operation,
+    denom: Option<String>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let is_owner = config.owner.as_ref() == Some(&info.sender);
+    let is_guardian = config.guardian.as_ref() == Some(&info.sender);
+
+    // The guardian exists to react quickly, lifting a pause is left to the owner
+    if paused && !is_owner && !is_guardian {
+        return Err(NotOwnerOrGuardian {});
+    }
+    if !paused && !is_owner {
+        return Err(NotOwner {});
+    }
+
+    save_pause(deps.storage, &operation, denom.as_deref(), paused)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_pause")
+        .add_attribute("operation", operation.as_str())
+        .add_attribute("denom", denom.unwrap_or_else(|| "*".to_string()))
+        .add_attribute("paused", paused.to_string()))
+}
+
+pub fn save_pause(
+    storage: &mut dyn Storage,
+    operation: &Operation,
+    denom: Option<&str>,
+    paused: bool,
+) -> StdResult<()> {
+    let key = (operation.as_str(), denom.unwrap_or_default());
+    if paused {
+        PAUSED.save(storage, key, operation)
+    } else {
+        PAUSED.remove(storage, key);
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::ContractError;
+use crate::error::ContractError::InvalidPayoutSplit;
+use crate::msg::Payout;
+use cosmwasm_std::{Api, Coin, Uint128, Uint256};
+
+// Keeps settlement of a split offer within a bounded number of bank messages
+pub const MAX_PAYOUT_RECIPIENTS: usize = 20;
+
+pub fn validate_payout_split(api: &dyn Api, split: &[Payout]) -> Result<(), ContractError> {
+    let invalid = |reason: &str| InvalidPayoutSplit {
+        reason: reason.to_string(),
+    };
+
+    if split.is_empty() || split.len() > MAX_PAYOUT_RECIPIENTS {
+        return Err(invalid(&format!(
+            "between 1 and {MAX_PAYOUT_RECIPIENTS} recipients are required"
+        )));
+    }
+    for (i, payout) in split.iter().enumerate() {
+        api.addr_validate(&payout.address)?;
+        if payout.weight == 0 {
+            return Err(invalid(&format!("{} has no weight", payout.address)));
+        }
+        if split[..i]
+            .iter()
+            .any(|other| other.address == payout.address)
+        {
+            return Err(invalid(&format!("{} is listed twice", payout.address)));
+        }
+    }
+    Ok(())
+}
+
+// Splits a coin by weight, rounding every share down and handing the units left over to the
+// recipients with the largest rounding losses, earlier recipients first on ties. The shares always
+// add up to the coin, and recipients whose share is zero are left out.
+pub fn split_payout(coin: &Coin, split: &[Payout]) -> Vec<(String, Coin)> {
+    let total_weight: Uint256 =
+        Uint128::new(split.iter().map(|payout| payout.weight as u128).sum()).into();
+
+    let mut shares = split
+        .iter()
+        .map(|payout| {
+            let weighted = coin.amount.full_mul(payout.weight as u128);
+            let share = weighted / total_weight;
+            let loss = weighted - share * total_weight;
+            // Shares can't exceed the coin amount, so they always fit in a Uint128
+            (Uint128::try_from(share).unwrap(), loss)
+        })
+        .collect::<Vec<_>>();
+
+    let distributed: Uint128 = shares.iter().map(|(share, _)| *share).sum();
+    let remainder = (coin.amount - distributed).u128() as usize;
+    let mut order = (0..shares.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| shares[*b].1.cmp(&shares[*a].1));
+    for i in order.into_iter().take(remainder) {
+        shares[i].0 += Uint128::one();
+    }
+
+    split
+        .iter()
+        .zip(shares)
+        .filter(|(_, (share, _))| !share.is_zero())
+        .map(|(payout, (share, _))| {
+            (
+                payout.address.clone(),
+                Coin {
+                    denom: coin.denom.clone(),
+                    amount: share,
+                },
+            )
+        })
+        .collect()
+}
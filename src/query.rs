@@ -1,7 +1,27 @@
-use crate::msg::Offer;
-use crate::state::{FULFILLED_OFFERS, OFFERS};
-use cosmwasm_std::{Deps, Order, StdError, StdResult};
+use crate::breakers::breaker_status;
+use crate::contract_offers::quote;
+use crate::denoms::denom_info;
+use crate::msg::{
+    Application, AuditEntry, BreakerStatus, Commitment, CommitmentConfig, ConfigResponse,
+    ContractOffer, Counter, DenomListing, FeesResponse, IbcChannelsResponse, IbcTransfer,
+    MakerBondConfig, MakerBondEntry, MakerLimits, MarketsResponse, MigrationProgress, Offer,
+    OfferWithDenoms, PairFee, Pause, ReferrerStatsResponse, ReservationConfig, RewardIndex,
+    StakerResponse, StakingStateResponse,
+};
+use crate::staking::pending_rewards;
+use crate::state::{
+    ACTION_CONTRACTS, ALLOWLIST_TAKEN, APPLICATIONS, APPROVED_IBC_CHANNELS, AUDIT_LOG, BONDED,
+    COMMITMENTS, COMMITMENT_CONFIG, CONFIG, CONTRACT_OFFERS, COUNTERS, DELISTED_DENOMS,
+    DENOM_LISTINGS, FULFILLED_OFFERS, IBC_TRANSFERS, MAKER_BONDS, MAKER_BOND_CONFIG, MAKER_LIMITS,
+    MARKETS, MARKETS_RESTRICTED, MIGRATION, OFFERS, OFFER_COUNTERS, OPEN_OFFERS, PAIR_FEES, PAUSED,
+    PENDING_OWNER, PROPOSER_COUNTERS, REFERRED_TRADES, REFERRER_EARNINGS,
+    REQUIRE_APPROVED_IBC_CHANNELS, RESERVATION_CONFIG, REWARD_INDEXES, STAKING_TOKEN, TOTAL_BONDED,
+    VOLUME_LIMITS,
+};
+use cosmwasm_std::{Coin as Coin1, Deps, Env, Order, StdError, StdResult, Uint128};
+use cw2::{get_contract_version, ContractVersion};
 use cw_storage_plus::Bound;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
 
 pub const DEFAULT_LIMIT: u32 = 10;
 
@@ -14,7 +34,8 @@ pub fn query_all_offers(
     let start = start_after
         .as_ref()
         .map(|denom| Bound::exclusive(denom.as_str()));
-    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let max_limit = CONFIG.load(deps.storage)?.max_query_limit;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize;
     OFFERS
         .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
@@ -22,9 +43,389 @@ pub fn query_all_offers(
         .collect()
 }
 
+// Query current offers along with where their denoms come from and how to display them
+pub fn query_offers_with_denoms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<OfferWithDenoms>> {
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let max_limit = CONFIG.load(deps.storage)?.max_query_limit;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize;
+    OFFERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|res| {
+            let (offer_id, offer) = res?;
+            Ok(OfferWithDenoms {
+                offer_id,
+                maker_denom: denom_info(&deps.querier, &offer.maker_coin.denom)?,
+                taker_denom: denom_info(&deps.querier, &offer.taker_coin.denom)?,
+                offer,
+            })
+        })
+        .collect()
+}
+
 // Query specific offers that have already been fulfilled
 pub fn query_fulfilled_offers(deps: Deps, offer_id: u64) -> Result<Option<Offer>, StdError> {
     let offer_id_str = offer_id.to_string();
     let offer = FULFILLED_OFFERS.may_load(deps.storage, &offer_id_str)?;
     Ok(offer)
 }
+
+// Query the state of the IBC return transfer for an offer taken from another chain
+pub fn query_ibc_transfer(deps: Deps, offer_id: String) -> StdResult<Option<IbcTransfer>> {
+    IBC_TRANSFERS.may_load(deps.storage, &offer_id)
+}
+
+// Query the protocol fee configuration
+pub fn query_fees(deps: Deps) -> StdResult<FeesResponse> {
+    let config = CONFIG.load(deps.storage)?.fees;
+    let pair_fees = PAIR_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| {
+            let ((maker_denom, taker_denom), fee_bps) = res?;
+            Ok(PairFee {
+                maker_denom,
+                taker_denom,
+                fee_bps,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FeesResponse {
+        fee_bps: config.fee_bps,
+        fee_recipient: config.fee_recipient.to_string(),
+        fee_payer: config.fee_payer,
+        pair_fees,
+        max_referral_fee_bps: config.max_referral_fee_bps,
+        referral_fee_source: config.referral_fee_source,
+    })
+}
+
+// Query the staking token, total bonded amount and reward index of every fee denom
+pub fn query_staking_state(deps: Deps) -> StdResult<StakingStateResponse> {
+    let reward_indexes = REWARD_INDEXES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(denom, index)| RewardIndex { denom, index }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(StakingStateResponse {
+        staking_token: STAKING_TOKEN.may_load(deps.storage)?,
+        total_bonded: TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default(),
+        reward_indexes,
+    })
+}
+
+// Query the amount a staker has bonded
+pub fn query_staker(deps: Deps, address: String) -> StdResult<StakerResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    Ok(StakerResponse {
+        address,
+        bonded: BONDED.may_load(deps.storage, &addr)?.unwrap_or_default(),
+    })
+}
+
+// Query the unclaimed fee rewards of a staker for every fee denom
+pub fn query_pending_rewards(deps: Deps, staker: String) -> StdResult<Vec<Coin>> {
+    let staker = deps.api.addr_validate(&staker)?;
+    Ok(pending_rewards(deps.storage, &staker)?
+        .into_iter()
+        .map(Coin::from)
+        .collect())
+}
+
+// Query how many trades a referrer brought in and the referral fees they earned
+pub fn query_referrer_stats(deps: Deps, referrer: String) -> StdResult<ReferrerStatsResponse> {
+    let addr = deps.api.addr_validate(&referrer)?;
+    let earned = REFERRER_EARNINGS
+        .prefix(&addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| {
+            let (denom, amount) = res?;
+            Ok(Coin {
+                denom,
+                amount: amount.to_string(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ReferrerStatsResponse {
+        referrer,
+        referred_trades: REFERRED_TRADES
+            .may_load(deps.storage, &addr)?
+            .unwrap_or_default(),
+        earned,
+    })
+}
+
+// Query the owner, any pending owner and the contract configuration
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|owner| owner.to_string()),
+        guardian: config.guardian.map(|guardian| guardian.to_string()),
+        pending_owner: PENDING_OWNER
+            .may_load(deps.storage)?
+            .map(|owner| owner.to_string()),
+        fee_bps: config.fees.fee_bps,
+        fee_recipient: config.fees.fee_recipient.to_string(),
+        fee_payer: config.fees.fee_payer,
+        max_referral_fee_bps: config.fees.max_referral_fee_bps,
+        referral_fee_source: config.fees.referral_fee_source,
+        max_query_limit: config.max_query_limit,
+        access_gate: config.access_gate,
+    })
+}
+
+// Query the cw2 contract name and version
+pub fn query_contract_info(deps: Deps) -> StdResult<ContractVersion> {
+    get_contract_version(deps.storage)
+}
+
+// Query the progress of a migration that is still re-encoding offers
+pub fn query_migration_progress(deps: Deps) -> StdResult<Option<MigrationProgress>> {
+    MIGRATION.may_load(deps.storage)
+}
+
+// Query every paused operation and the denom it is paused for
+pub fn query_pauses(deps: Deps) -> StdResult<Vec<Pause>> {
+    PAUSED
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| {
+            let ((_, denom), operation) = res?;
+            Ok(Pause {
+                operation,
+                denom: (!denom.is_empty()).then_some(denom),
+            })
+        })
+        .collect()
+}
+
+// Query the denoms delisted by governance
+pub fn query_delisted_denoms(deps: Deps) -> StdResult<Vec<String>> {
+    DELISTED_DENOMS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+// Query the denom lists and every configured market
+pub fn query_markets(deps: Deps) -> StdResult<MarketsResponse> {
+    let mut allowed_denoms = vec![];
+    let mut blocked_denoms = vec![];
+    for res in DENOM_LISTINGS.range(deps.storage, None, None, Order::Ascending) {
+        match res? {
+            (denom, DenomListing::Allowed) => allowed_denoms.push(denom),
+            (denom, DenomListing::Blocked) => blocked_denoms.push(denom),
+        }
+    }
+
+    Ok(MarketsResponse {
+        restricted: MARKETS_RESTRICTED
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        allowed_denoms,
+        blocked_denoms,
+        markets: MARKETS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| Ok(res?.1))
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
+// Query the approved IBC channels and whether approval is required
+pub fn query_ibc_channels(deps: Deps) -> StdResult<IbcChannelsResponse> {
+    Ok(IbcChannelsResponse {
+        require_approved: REQUIRE_APPROVED_IBC_CHANNELS
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        approved: APPROVED_IBC_CHANNELS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
+// Query the pending applicants of an approval offer
+pub fn query_applicants(deps: Deps, offer_id: String) -> StdResult<Vec<Application>> {
+    APPLICATIONS
+        .prefix(&offer_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, application)| application))
+        .collect()
+}
+
+// Query the counters proposed for an offer
+pub fn query_counters_by_offer(
+    deps: Deps,
+    offer_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Counter>> {
+    let max_limit = CONFIG.load(deps.storage)?.max_query_limit;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize;
+    OFFER_COUNTERS
+        .prefix(&offer_id)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|counter_id| COUNTERS.load(deps.storage, counter_id?))
+        .collect()
+}
+
+// Query the counters an address has proposed
+pub fn query_counters_by_proposer(
+    deps: Deps,
+    proposer: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Counter>> {
+    let proposer = deps.api.addr_validate(&proposer)?;
+    let max_limit = CONFIG.load(deps.storage)?.max_query_limit;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize;
+    PROPOSER_COUNTERS
+        .prefix(&proposer)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|counter_id| COUNTERS.load(deps.storage, counter_id?))
+        .collect()
+}
+
+// Query a contract offer
+pub fn query_contract_offer(deps: Deps, offer_id: String) -> StdResult<Option<ContractOffer>> {
+    CONTRACT_OFFERS.may_load(deps.storage, &offer_id)
+}
+
+// Query the maker amount a contract offer's maker quotes for the taker coin
+pub fn query_contract_offer_quote(
+    deps: Deps,
+    offer_id: String,
+    taker: String,
+    taker_coin: Coin1,
+) -> StdResult<Uint128> {
+    let offer = CONTRACT_OFFERS.load(deps.storage, &offer_id)?;
+    let taker = deps.api.addr_validate(&taker)?;
+    Ok(quote(
+        &deps.querier,
+        &offer_id,
+        &offer,
+        taker.as_str(),
+        &taker_coin,
+    )?
+    .0)
+}
+
+// Query the contracts approved for proceeds actions
+pub fn query_action_contracts(deps: Deps) -> StdResult<Vec<String>> {
+    ACTION_CONTRACTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|res| Ok(res?.to_string()))
+        .collect()
+}
+
+// Query the limits on offers per maker
+pub fn query_maker_limits(deps: Deps) -> StdResult<MakerLimits> {
+    Ok(MAKER_LIMITS.may_load(deps.storage)?.unwrap_or_default())
+}
+
+// Query a pending commitment to take an offer
+pub fn query_commitment(deps: Deps, commitment: String) -> StdResult<Option<Commitment>> {
+    COMMITMENTS.may_load(deps.storage, &commitment.to_lowercase())
+}
+
+// Query the deposit posted with commitments, if takers can commit
+pub fn query_commitment_config(deps: Deps) -> StdResult<Option<CommitmentConfig>> {
+    COMMITMENT_CONFIG.may_load(deps.storage)
+}
+
+// Query the bond makers have to post and the share slashed when settlement fails
+pub fn query_maker_bond_config(deps: Deps) -> StdResult<MakerBondConfig> {
+    Ok(MAKER_BOND_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default())
+}
+
+// Query the bonds a maker has posted with their offers
+pub fn query_maker_bonds(
+    deps: Deps,
+    maker: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<MakerBondEntry>> {
+    let maker = deps.api.addr_validate(&maker)?;
+    let max_limit = CONFIG.load(deps.storage)?.max_query_limit;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize;
+    MAKER_BONDS
+        .prefix(&maker)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect()
+}
+
+// Query the deposit and longest window of reservations, if enabled
+pub fn query_reservation_config(deps: Deps) -> StdResult<Option<ReservationConfig>> {
+    RESERVATION_CONFIG.may_load(deps.storage)
+}
+
+// Query how many open offers a maker has
+pub fn query_maker_open_offers(deps: Deps, maker: String) -> StdResult<u32> {
+    let maker = deps.api.addr_validate(&maker)?;
+    Ok(OPEN_OFFERS
+        .may_load(deps.storage, &maker)?
+        .unwrap_or_default())
+}
+
+// Query every volume limit with its current window usage and breaker status
+pub fn query_breakers(deps: Deps, env: Env) -> StdResult<Vec<BreakerStatus>> {
+    VOLUME_LIMITS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| breaker_status(deps.storage, res?.1, env.block.time))
+        .collect()
+}
+
+// Query how much of the maker coin an address has taken from offers with an allowlist
+pub fn query_allowlist_taken(
+    deps: Deps,
+    merkle_root: String,
+    address: String,
+) -> StdResult<Uint128> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(ALLOWLIST_TAKEN
+        .may_load(deps.storage, (&merkle_root.to_lowercase(), &address))?
+        .unwrap_or_default())
+}
+
+// Query the governance audit log, oldest actions first
+pub fn query_audit_log(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AuditEntry>> {
+    let max_limit = CONFIG.load(deps.storage)?.max_query_limit;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(max_limit) as usize;
+    AUDIT_LOG
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|res| Ok(res?.1))
+        .collect()
+}
@@ -0,0 +1,130 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    InaccurateFunds, InvalidReservationBlocks, InvalidReservationConfig, NoOfferFound,
+    OfferExpired, OfferReserved, ReservationsDisabled,
+};
+use crate::execute::assert_owner;
+use crate::migrate::assert_not_migrating;
+use crate::msg::{Offer, Operation, Reservation, ReservationConfig};
+use crate::pause::assert_not_paused;
+use crate::state::{OFFERS, RESERVATION_CONFIG};
+use cosmwasm_std::{Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, StdResult};
+
+fn is_live(env: &Env, reservation: &Reservation) -> bool {
+    env.block.height < reservation.expires_at_height
+}
+
+// Offers under a live reservation can only be taken by their reserver
+pub fn assert_not_reserved(env: &Env, offer: &Offer, taker: &Addr) -> Result<(), ContractError> {
+    match &offer.reservation {
+        Some(reservation) if is_live(env, reservation) && reservation.reserver != *taker => {
+            Err(OfferReserved {
+                reserver: reservation.reserver.clone(),
+                expires_at_height: reservation.expires_at_height,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+// Pays out the deposit of an offer's reservation. Lapsed deposits are forfeited to the maker,
+// while live ones are returned to the reserver, who either took the offer or lost it to a close.
+pub fn release_reservation(env: &Env, offer: &Offer) -> StdResult<Option<BankMsg>> {
+    offer
+        .reservation
+        .as_ref()
+        .map(|reservation| {
+            let to_address = match is_live(env, reservation) {
+                true => reservation.reserver.clone(),
+                false => offer.maker.clone(),
+            };
+            Ok(BankMsg::Send {
+                to_address,
+                amount: vec![Coin::try_from(reservation.deposit.clone())?],
+            })
+        })
+        .transpose()
+}
+
+pub fn reserve_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: String,
+    blocks: u64,
+) -> Result<Response, ContractError> {
+    assert_not_migrating(deps.storage)?;
+
+    let mut offer = OFFERS
+        .may_load(deps.storage, &offer_id)?
+        .ok_or(NoOfferFound {})?;
+    assert_not_paused(
+        deps.storage,
+        Operation::Take,
+        &[&offer.maker_coin.denom, &offer.taker_coin.denom],
+    )?;
+    if offer
+        .expires_at
+        .is_some_and(|expires_at| env.block.time >= expires_at)
+    {
+        return Err(OfferExpired {});
+    }
+
+    let config = RESERVATION_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ReservationsDisabled {})?;
+    if blocks == 0 || blocks > config.max_blocks {
+        return Err(InvalidReservationBlocks {
+            max_blocks: config.max_blocks,
+        });
+    }
+    // Reservations can't be extended, so other takers get their turn once one lapses
+    if let Some(reservation) = offer.reservation.as_ref().filter(|r| is_live(&env, r)) {
+        return Err(OfferReserved {
+            reserver: reservation.reserver.clone(),
+            expires_at_height: reservation.expires_at_height,
+        });
+    }
+    if info.funds != [config.deposit.clone()] {
+        return Err(InaccurateFunds {});
+    }
+
+    let mut response = Response::new();
+    if let Some(forfeit) = release_reservation(&env, &offer)? {
+        response = response.add_message(forfeit);
+    }
+
+    let expires_at_height = env.block.height + blocks;
+    offer.reservation = Some(Reservation {
+        reserver: info.sender.to_string(),
+        deposit: config.deposit.into(),
+        expires_at_height,
+    });
+    OFFERS.save(deps.storage, &offer_id, &offer)?;
+
+    Ok(response
+        .add_attribute("action", "reserve_offer")
+        .add_attribute("offer_id", offer_id)
+        .add_attribute("reserver", info.sender)
+        .add_attribute("expires_at_height", expires_at_height.to_string()))
+}
+
+pub fn update_reservation_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<ReservationConfig>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match config {
+        Some(config) => {
+            if config.deposit.amount.is_zero() || config.max_blocks == 0 {
+                return Err(InvalidReservationConfig {});
+            }
+            RESERVATION_CONFIG.save(deps.storage, &config)?;
+        }
+        None => RESERVATION_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_reservation_config"))
+}
@@ -0,0 +1,120 @@
+use crate::msg::StakingToken;
+use crate::state::{
+    BONDED, REWARD_INDEXES, STAKER_REWARDS, STAKER_REWARD_INDEXES, STAKING_TOKEN, TOTAL_BONDED,
+};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, Order, StdResult, Storage, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+pub fn validate_staking_token(deps: Deps, token: StakingToken) -> StdResult<StakingToken> {
+    Ok(match token {
+        StakingToken::Native { denom } => StakingToken::Native { denom },
+        StakingToken::Cw20 { contract_addr } => StakingToken::Cw20 {
+            contract_addr: deps.api.addr_validate(&contract_addr)?.to_string(),
+        },
+    })
+}
+
+// Adds a settlement fee to the reward index of its denom. Returns false when there is nobody
+// to distribute to, in which case the fee goes to the fee recipient instead.
+pub fn distribute_fee(storage: &mut dyn Storage, fee: &Coin) -> StdResult<bool> {
+    if fee.amount.is_zero() || STAKING_TOKEN.may_load(storage)?.is_none() {
+        return Ok(false);
+    }
+
+    let total_bonded = TOTAL_BONDED.may_load(storage)?.unwrap_or_default();
+    if total_bonded.is_zero() {
+        return Ok(false);
+    }
+
+    let index = REWARD_INDEXES
+        .may_load(storage, &fee.denom)?
+        .unwrap_or_default();
+    REWARD_INDEXES.save(
+        storage,
+        &fee.denom,
+        &(index + Decimal::from_ratio(fee.amount, total_bonded)),
+    )?;
+
+    Ok(true)
+}
+
+// Moves everything a staker earned since their last settlement into their unclaimed rewards.
+// Must run before their bonded amount changes.
+pub fn settle_rewards(storage: &mut dyn Storage, staker: &Addr) -> StdResult<Uint128> {
+    let bonded = BONDED.may_load(storage, staker)?.unwrap_or_default();
+
+    let indexes = REWARD_INDEXES
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (denom, index) in indexes {
+        let last_index = STAKER_REWARD_INDEXES
+            .may_load(storage, (staker, &denom))?
+            .unwrap_or_default();
+        if index == last_index {
+            continue;
+        }
+
+        let accrued = bonded * (index - last_index);
+        if !accrued.is_zero() {
+            STAKER_REWARDS.update(storage, (staker, &denom), |rewards| -> StdResult<_> {
+                Ok(rewards.unwrap_or_default() + accrued)
+            })?;
+        }
+        STAKER_REWARD_INDEXES.save(storage, (staker, &denom), &index)?;
+    }
+
+    Ok(bonded)
+}
+
+// Unclaimed rewards including what has accrued since the last settlement, without writing state
+pub fn pending_rewards(storage: &dyn Storage, staker: &Addr) -> StdResult<Vec<Coin>> {
+    let bonded = BONDED.may_load(storage, staker)?.unwrap_or_default();
+
+    REWARD_INDEXES
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| {
+            let (denom, index) = res?;
+            let last_index = STAKER_REWARD_INDEXES
+                .may_load(storage, (staker, &denom))?
+                .unwrap_or_default();
+            let settled = STAKER_REWARDS
+                .may_load(storage, (staker, &denom))?
+                .unwrap_or_default();
+            Ok(Coin {
+                amount: settled + bonded * (index - last_index),
+                denom,
+            })
+        })
+        .filter(|res| !matches!(res, Ok(coin) if coin.amount.is_zero()))
+        .collect()
+}
+
+pub fn staking_token_transfer_msg(
+    token: &StakingToken,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(match token {
+        StakingToken::Native { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+        StakingToken::Cw20 { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
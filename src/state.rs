@@ -1,6 +1,168 @@
-use crate::msg::Offer;
+use crate::msg::{
+    AccessGate, Application, AuditEntry, Commitment, CommitmentConfig, ContractOffer, Counter,
+    DenomListing, FeePayer, IbcReceiver, IbcTransfer, MakerAsset, MakerBondConfig, MakerBondEntry,
+    MakerLimits, Market, MigrationProgress, Offer, Operation, ProceedsAction, ReferralFeeSource,
+    ReservationConfig, StakingToken, VolumeLimit,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Decimal, SubMsg, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
+#[cw_serde]
+pub struct FeeConfig {
+    pub fee_bps: u16,
+    pub fee_recipient: Addr,
+    pub fee_payer: FeePayer,
+    pub max_referral_fee_bps: u16,
+    pub referral_fee_source: ReferralFeeSource,
+}
+
+#[cw_serde]
+pub struct Config {
+    /// None once ownership has been renounced
+    pub owner: Option<Addr>,
+    /// Can pause operations, but only the owner can unpause them
+    pub guardian: Option<Addr>,
+    pub fees: FeeConfig,
+    /// Largest page size returned by paginated queries
+    pub max_query_limit: u32,
+    /// Contract every maker and taker has to be admitted by
+    pub access_gate: Option<AccessGate>,
+}
+
+// Settlement of a flash take, waiting for the taker to repay during its callback
+#[cw_serde]
+pub struct FlashSettlement {
+    pub offer_id: String,
+    pub taker_coin: Coin,
+    pub repaid: bool,
+    /// Payouts of the taker coin, sent once it has been repaid
+    pub payouts: Vec<SubMsg>,
+}
+
+// Settlement of a bonded offer waiting for its reply, refunded if the maker can't be pulled from
+#[cw_serde]
+pub struct PendingSettlement {
+    pub offer_id: String,
+    pub taker: String,
+    pub refund: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct PendingFill {
+    pub offer_id: String,
+    pub taker: String,
+    pub maker_asset: MakerAsset,
+    pub maker_amount: Uint128,
+    /// Taker balance of the maker asset the fill has to reach
+    pub expected_balance: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingAction {
+    pub offer_id: String,
+    pub maker: String,
+    pub action: ProceedsAction,
+    pub proceeds: Coin,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+// Owner proposed by the current owner, who becomes owner once they accept
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
+// Fee overrides keyed by (maker denom, taker denom)
+pub const PAIR_FEES: Map<(&str, &str), u16> = Map::new("pair_fees");
+// Referral fees earned, keyed by (referrer, denom)
+pub const REFERRER_EARNINGS: Map<(&Addr, &str), Uint128> = Map::new("referrer_earnings");
+pub const REFERRED_TRADES: Map<&Addr, u64> = Map::new("referred_trades");
+
+// Paused operations keyed by (operation, denom), with an empty denom pausing every denom
+pub const PAUSED: Map<(&str, &str), Operation> = Map::new("paused");
+
+// Denoms on the allowlist or blocklist
+pub const DENOM_LISTINGS: Map<&str, DenomListing> = Map::new("denom_listings");
+// Markets keyed by (maker denom, taker denom)
+pub const MARKETS: Map<(&str, &str), Market> = Map::new("markets");
+// Whether only allowlisted denoms in enabled markets can trade, false when unset
+pub const MARKETS_RESTRICTED: Item<bool> = Item::new("markets_restricted");
+
+// Channels IBC denoms may arrive through, enforced while approval is required
+pub const APPROVED_IBC_CHANNELS: Map<&str, bool> = Map::new("approved_ibc_channels");
+pub const REQUIRE_APPROVED_IBC_CHANNELS: Item<bool> = Item::new("require_approved_ibc_channels");
+
+// Denoms governance removed from trading
+pub const DELISTED_DENOMS: Map<&str, bool> = Map::new("delisted_denoms");
+// Governance actions taken through sudo, keyed by sequential id
+pub const AUDIT_LOG: Map<u64, AuditEntry> = Map::new("audit_log");
+pub const AUDIT_LOG_COUNTER: Item<u64> = Item::new("audit_log_counter");
+
+pub const MAKER_LIMITS: Item<MakerLimits> = Item::new("maker_limits");
+pub const OPEN_OFFERS: Map<&Addr, u32> = Map::new("open_offers");
+// Creation window (block height or hour) a maker last made offers in, and how many
+pub const OFFERS_CREATED: Map<&Addr, (u64, u32)> = Map::new("offers_created");
+// Offers that expire, keyed by (expiry in seconds, offer id)
+pub const OFFER_EXPIRIES: Map<(u64, &str), bool> = Map::new("offer_expiries");
+
+// Pending commitments keyed by their hash, and by offer and sequence to find earlier ones
+pub const COMMITMENTS: Map<&str, Commitment> = Map::new("commitments");
+pub const COMMITMENT_SEQUENCE: Map<(&str, u64), String> = Map::new("commitment_sequence");
+pub const COMMITMENT_COUNTER: Item<u64> = Item::new("commitment_counter");
+pub const COMMITMENT_CONFIG: Item<CommitmentConfig> = Item::new("commitment_config");
+
+pub const MAKER_BOND_CONFIG: Item<MakerBondConfig> = Item::new("maker_bond_config");
+// Bonds posted with offers, keyed by (maker, offer id)
+pub const MAKER_BONDS: Map<(&Addr, u64), MakerBondEntry> = Map::new("maker_bonds");
+pub const PENDING_SETTLEMENT: Item<PendingSettlement> = Item::new("pending_settlement");
+
+// Deposit and longest window of offer reservations, unset while reservations are disabled
+pub const RESERVATION_CONFIG: Item<ReservationConfig> = Item::new("reservation_config");
+
+// Volume limits keyed by denom
+pub const VOLUME_LIMITS: Map<&str, VolumeLimit> = Map::new("volume_limits");
+// Settled volume keyed by (denom, window bucket)
+pub const WINDOW_VOLUMES: Map<(&str, u64), Uint128> = Map::new("window_volumes");
+// When the breaker of a denom tripped, until the owner resets it
+pub const TRIPPED_BREAKERS: Map<&str, Timestamp> = Map::new("tripped_breakers");
+
+// Maker coin amount taken from allowlisted offers, keyed by (merkle root, taker)
+pub const ALLOWLIST_TAKEN: Map<(&str, &Addr), Uint128> = Map::new("allowlist_taken");
+
 pub const OFFER_ID_COUNTER: Item<u64> = Item::new("offer_id_counter");
 pub const OFFERS: Map<&str, Offer> = Map::new("offer");
 pub const FULFILLED_OFFERS: Map<&str, Offer> = Map::new("fulfilled_offers");
+
+// Takers that arrived through ibc-hooks, keyed by offer id until the offer is fulfilled
+pub const IBC_TAKERS: Map<&str, IbcReceiver> = Map::new("ibc_takers");
+// Return transfers of maker coins to IBC takers, keyed by offer id
+pub const IBC_TRANSFERS: Map<&str, IbcTransfer> = Map::new("ibc_transfers");
+// Lookup from (channel, sequence) of an in-flight transfer to its offer id
+pub const IBC_TRANSFER_SEQUENCES: Map<(&str, u64), String> = Map::new("ibc_transfer_sequences");
+pub const FLASH_SETTLEMENT: Item<FlashSettlement> = Item::new("flash_settlement");
+// Pending applicants of approval offers
+pub const APPLICATIONS: Map<(&str, &Addr), Application> = Map::new("applications");
+pub const COUNTERS: Map<u64, Counter> = Map::new("counters");
+pub const COUNTER_ID_COUNTER: Item<u64> = Item::new("counter_id_counter");
+// Open counters by offer and by proposer
+pub const OFFER_COUNTERS: Map<(&str, u64), bool> = Map::new("offer_counters");
+pub const PROPOSER_COUNTERS: Map<(&Addr, u64), bool> = Map::new("proposer_counters");
+pub const CONTRACT_OFFERS: Map<&str, ContractOffer> = Map::new("contract_offers");
+// Maker transfer fill waiting for its ConfirmFill reply
+pub const PENDING_FILL: Item<PendingFill> = Item::new("pending_fill");
+// Proceeds action waiting for its reply
+pub const PENDING_ACTION: Item<PendingAction> = Item::new("pending_action");
+// Contracts makers can send their proceeds to
+pub const ACTION_CONTRACTS: Map<&Addr, bool> = Map::new("action_contracts");
+// Offer id of the transfer waiting for its MsgTransfer reply
+pub const INFLIGHT_IBC_TRANSFER: Item<String> = Item::new("inflight_ibc_transfer");
+
+pub const STAKING_TOKEN: Item<StakingToken> = Item::new("staking_token");
+pub const TOTAL_BONDED: Item<Uint128> = Item::new("total_bonded");
+pub const BONDED: Map<&Addr, Uint128> = Map::new("bonded");
+// Accumulated fees per bonded token, keyed by fee denom
+pub const REWARD_INDEXES: Map<&str, Decimal> = Map::new("reward_indexes");
+// Reward index of each fee denom at the time a staker's rewards were last settled
+pub const STAKER_REWARD_INDEXES: Map<(&Addr, &str), Decimal> = Map::new("staker_reward_indexes");
+// Settled but unclaimed rewards, keyed by (staker, fee denom)
+pub const STAKER_REWARDS: Map<(&Addr, &str), Uint128> = Map::new("staker_rewards");
+
+// Progress of a migration that re-encodes stored offers over several transactions
+pub const MIGRATION: Item<MigrationProgress> = Item::new("migration");
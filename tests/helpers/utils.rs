@@ -1,14 +1,30 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
-use atomic_swaps_contract::msg::InstantiateMsg;
-use osmosis_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
-use osmosis_test_tube::{Bank, OsmosisTestApp, RunnerError, SigningAccount, Wasm};
+use atomic_swaps_contract::msg::{
+    AccessGate, AllowlistProof, ExecuteMsg, FeePayer, HoldingRequirement, InstantiateMsg,
+    MigrateMsg, Payout, ProceedsAction, Referral, ReferralFeeSource, SudoMsg, TakerAllowlist,
+    TakerCallback,
+};
+use cosmwasm_std::{coin, CosmosMsg};
+use osmosis_std::shim::Timestamp;
+use osmosis_std::types::cosmos::authz::v1beta1::{Grant, MsgGrant, MsgGrantResponse};
+use osmosis_std::types::cosmos::bank::v1beta1::{QueryBalanceRequest, SendAuthorization};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_std::types::cosmwasm::wasm::v1::{
+    AllowAllMessagesFilter, ContractExecutionAuthorization, ContractGrant, MaxFundsLimit,
+    MsgMigrateContract, MsgMigrateContractResponse, SudoContractProposal,
+};
+use osmosis_test_tube::{
+    Account, Bank, GovWithAppAccess, Module, OsmosisTestApp, Runner, RunnerError,
+    RunnerExecuteResult, SigningAccount, Wasm,
+};
+use prost::Message;
 
 pub fn wasm_file() -> Vec<u8> {
-    let wasm_file_path = format!("./artifacts/atomic_swaps_contract");
+    let wasm_file_path = "./artifacts/atomic_swaps_contract";
 
-    match std::fs::read(wasm_file_path.clone()) {
+    match std::fs::read(format!("{wasm_file_path}.wasm")) {
         Ok(bytes) => {
             println!("{wasm_file_path}.wasm");
             bytes
@@ -22,7 +38,100 @@ pub fn wasm_file() -> Vec<u8> {
     }
 }
 
+// v1 of the contract, built from the first release and stored next to the current artifact
+pub fn v1_wasm_file() -> Vec<u8> {
+    std::fs::read("./artifacts/v1/atomic_swaps_contract.wasm").unwrap()
+}
+
+// cw4-group from cw-plus, used as an access gate
+pub fn cw4_group_wasm_file() -> Vec<u8> {
+    std::fs::read("./artifacts/cw4_group.wasm").unwrap()
+}
+
+// cw20-base from cw-plus, used for cw20 holding requirements
+pub fn cw20_base_wasm_file() -> Vec<u8> {
+    std::fs::read("./artifacts/cw20_base.wasm").unwrap()
+}
+
+// cw721-base from cw-nfts, used for cw721 holding requirements
+pub fn cw721_base_wasm_file() -> Vec<u8> {
+    std::fs::read("./artifacts/cw721_base.wasm").unwrap()
+}
+
+// Reference maker contract from contracts/jit-maker
+pub fn jit_maker_wasm_file() -> Vec<u8> {
+    std::fs::read("./artifacts/jit_maker.wasm").unwrap()
+}
+
+#[allow(clippy::result_large_err)]
+pub fn migrate_contract(
+    app: &OsmosisTestApp,
+    admin: &SigningAccount,
+    contract_addr: &str,
+    code_id: u64,
+    msg: &MigrateMsg,
+) -> RunnerExecuteResult<MsgMigrateContractResponse> {
+    app.execute::<_, MsgMigrateContractResponse>(
+        MsgMigrateContract {
+            sender: admin.address(),
+            contract: contract_addr.to_string(),
+            code_id,
+            msg: serde_json::to_vec(msg).unwrap(),
+        },
+        "/cosmwasm.wasm.v1.MsgMigrateContract",
+        admin,
+    )
+}
+
+// Passes a governance proposal that calls the contract's sudo entry point
+pub fn governance_sudo(
+    app: &OsmosisTestApp,
+    contract_addr: &str,
+    msg: &SudoMsg,
+    proposer: &SigningAccount,
+) {
+    GovWithAppAccess::new(app)
+        .propose_and_execute(
+            SudoContractProposal::TYPE_URL.to_string(),
+            SudoContractProposal {
+                title: "atomic swaps sudo".to_string(),
+                description: "atomic swaps sudo".to_string(),
+                contract: contract_addr.to_string(),
+                msg: serde_json::to_vec(msg).unwrap(),
+            },
+            proposer.address(),
+            false,
+            proposer,
+        )
+        .unwrap();
+}
+
+// Fee free configuration with the owner as fee recipient, tests override the fields they need
+pub fn default_instantiate_msg(owner: &SigningAccount) -> InstantiateMsg {
+    InstantiateMsg {
+        owner: None,
+        guardian: None,
+        max_query_limit: 30,
+        access_gate: None,
+        fee_bps: 0,
+        fee_recipient: owner.address(),
+        fee_payer: FeePayer::Maker,
+        pair_fees: vec![],
+        staking_token: None,
+        max_referral_fee_bps: 0,
+        referral_fee_source: ReferralFeeSource::ProtocolFee,
+    }
+}
+
 pub fn instantiate_contract(wasm: &Wasm<OsmosisTestApp>, owner: &SigningAccount) -> String {
+    instantiate_contract_with_msg(wasm, owner, &default_instantiate_msg(owner))
+}
+
+pub fn instantiate_contract_with_msg(
+    wasm: &Wasm<OsmosisTestApp>,
+    owner: &SigningAccount,
+    msg: &InstantiateMsg,
+) -> String {
     let code_id = wasm
         .store_code(&wasm_file(), None, owner)
         .unwrap()
@@ -31,7 +140,7 @@ pub fn instantiate_contract(wasm: &Wasm<OsmosisTestApp>, owner: &SigningAccount)
 
     wasm.instantiate(
         code_id,
-        &InstantiateMsg {},
+        msg,
         None,
         Some("atomic-swaps-contract"),
         &[],
@@ -42,6 +151,180 @@ pub fn instantiate_contract(wasm: &Wasm<OsmosisTestApp>, owner: &SigningAccount)
     .address
 }
 
+// Accounts holding uatom, uosmo and uion, with the first as a maker that granted the contract
+// authz and the last as the owner that instantiated it
+pub fn setup_contract(app: &OsmosisTestApp, accounts: u64) -> (Vec<SigningAccount>, String) {
+    let wasm = Wasm::new(app);
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+                coin(1_000_000_000_000, "uion"),
+            ],
+            accounts,
+        )
+        .unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, accs.last().unwrap());
+    grant_authz(
+        app,
+        &accs[0],
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000, "uatom")),
+    );
+
+    (accs, contract_addr)
+}
+
+// Offer of 1_000_000 uatom for 1_000_000 uosmo, tests override the fields they exercise
+pub struct MakeOffer {
+    pub maker_coin: Coin,
+    pub taker_coin: Coin,
+    pub referral: Option<Referral>,
+    pub access_gate: Option<AccessGate>,
+    pub taker_allowlist: Option<TakerAllowlist>,
+    pub holding_requirements: Option<Vec<HoldingRequirement>>,
+    pub proceeds_recipient: Option<String>,
+    pub payout_split: Option<Vec<Payout>>,
+    pub proceeds_action: Option<ProceedsAction>,
+    pub approval_window: Option<u64>,
+    pub reveal_delay: Option<u64>,
+    pub bond: Option<cosmwasm_std::Coin>,
+}
+
+impl Default for MakeOffer {
+    fn default() -> Self {
+        MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        }
+    }
+}
+
+impl From<MakeOffer> for ExecuteMsg {
+    fn from(offer: MakeOffer) -> Self {
+        ExecuteMsg::MakeOffer {
+            maker_coin: offer.maker_coin,
+            taker_coin: offer.taker_coin,
+            referral: offer.referral,
+            access_gate: offer.access_gate,
+            taker_allowlist: offer.taker_allowlist,
+            holding_requirements: offer.holding_requirements,
+            proceeds_recipient: offer.proceeds_recipient,
+            payout_split: offer.payout_split,
+            proceeds_action: offer.proceeds_action,
+            approval_window: offer.approval_window,
+            reveal_delay: offer.reveal_delay,
+            bond: offer.bond,
+        }
+    }
+}
+
+// Take of the first offer, tests override the fields they exercise
+pub struct TakeOffer {
+    pub offer_id: String,
+    pub referral: Option<Referral>,
+    pub allowlist_proof: Option<AllowlistProof>,
+    pub recipient: Option<String>,
+    pub callback: Option<TakerCallback>,
+}
+
+impl Default for TakeOffer {
+    fn default() -> Self {
+        TakeOffer {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        }
+    }
+}
+
+impl From<TakeOffer> for ExecuteMsg {
+    fn from(take: TakeOffer) -> Self {
+        ExecuteMsg::OfferTaker {
+            offer_id: take.offer_id,
+            referral: take.referral,
+            allowlist_proof: take.allowlist_proof,
+            recipient: take.recipient,
+            callback: take.callback,
+        }
+    }
+}
+
+// Grants the contract the execution and send authorizations a maker needs before making offers
+pub fn grant_authz(
+    app: &OsmosisTestApp,
+    granter: &SigningAccount,
+    contract_addr: &str,
+    spend_limit: Coin,
+) {
+    let expiration = Some(Timestamp {
+        seconds: 3150000000,
+        nanos: 0,
+    });
+
+    let execution_grant = MsgGrant {
+        granter: granter.address(),
+        grantee: contract_addr.to_string(),
+        grant: Some(Grant {
+            authorization: Some(
+                ContractExecutionAuthorization {
+                    grants: vec![ContractGrant {
+                        contract: contract_addr.to_string(),
+                        limit: Some(
+                            MaxFundsLimit {
+                                amounts: vec![spend_limit.clone()],
+                            }
+                            .to_any(),
+                        ),
+                        filter: Some(AllowAllMessagesFilter {}.to_any()),
+                    }],
+                }
+                .to_any(),
+            ),
+            expiration: expiration.clone(),
+        }),
+    };
+
+    let send_grant = MsgGrant {
+        granter: granter.address(),
+        grantee: contract_addr.to_string(),
+        grant: Some(Grant {
+            authorization: Some(
+                SendAuthorization {
+                    spend_limit: vec![spend_limit],
+                }
+                .to_any(),
+            ),
+            expiration,
+        }),
+    };
+
+    let msgs: Vec<CosmosMsg> = [execution_grant, send_grant]
+        .into_iter()
+        .map(|grant| CosmosMsg::Stargate {
+            type_url: "/cosmos.authz.v1beta1.MsgGrant".to_string(),
+            value: grant.encode_to_vec().into(),
+        })
+        .collect();
+
+    app.execute_cosmos_msgs::<MsgGrantResponse>(&msgs, granter)
+        .unwrap();
+}
+
 pub fn query_balance(bank: &Bank<OsmosisTestApp>, addr: &str, denom: &str) -> u128 {
     bank.query_balance(&QueryBalanceRequest {
         address: addr.to_string(),
@@ -0,0 +1,121 @@
+use crate::helpers::{
+    assert_err, cw4_group_wasm_file, query_balance, setup_contract, MakeOffer, TakeOffer,
+};
+use atomic_swaps_contract::error::ContractError::{
+    ActionContractNotApproved, InvalidProceedsAction,
+};
+use atomic_swaps_contract::msg::{ExecuteMsg, ProceedsAction, QueryMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+use serde_json::json;
+
+pub mod helpers;
+
+fn make_offer_msg(proceeds_action: ProceedsAction) -> ExecuteMsg {
+    ExecuteMsg::from(MakeOffer {
+        taker_coin: Coin::from(coin(2_000_000, "uosmo")),
+        proceeds_action: Some(proceeds_action),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn failed_proceeds_actions_are_refunded_to_the_maker() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+
+    // A cw4 group stands in for a vault, and rejects the deposit it doesn't understand
+    let code_id = wasm
+        .store_code(&cw4_group_wasm_file(), None, admin)
+        .unwrap()
+        .data
+        .code_id;
+    let vault = wasm
+        .instantiate(
+            code_id,
+            &json!({ "admin": admin.address(), "members": [] }),
+            None,
+            Some("vault"),
+            &[],
+            admin,
+        )
+        .unwrap()
+        .data
+        .address;
+    let deposit = ProceedsAction::WasmExecute {
+        contract: vault.clone(),
+        msg: r#"{"deposit":{"amount":"{{amount}}","owner":"{{maker}}"}}"#.to_string(),
+    };
+
+    let err_res = wasm
+        .execute(&contract_addr, &make_offer_msg(deposit.clone()), &[], maker)
+        .unwrap_err();
+    assert_err(
+        err_res,
+        ActionContractNotApproved {
+            contract: vault.clone(),
+        },
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateActionContracts {
+            approve: vec![vault.clone()],
+            revoke: vec![],
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+    let approved: Vec<String> = wasm
+        .query(&contract_addr, &QueryMsg::ActionContracts {})
+        .unwrap();
+    assert_eq!(approved, vec![vault.clone()]);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &make_offer_msg(ProceedsAction::WasmExecute {
+                contract: vault,
+                msg: "{\"deposit\":".to_string(),
+            }),
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        InvalidProceedsAction {
+            reason: "msg is not a valid JSON template".to_string(),
+        },
+    );
+
+    wasm.execute(&contract_addr, &make_offer_msg(deposit), &[], maker)
+        .unwrap();
+
+    let maker_balance = query_balance(&bank, &maker.address(), "uosmo");
+    let res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::from(TakeOffer::default()),
+            &[coin(2_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap();
+
+    // The take still settles and the maker gets the proceeds back
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uosmo"),
+        maker_balance + 2_000_000
+    );
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "proceeds_action_failed" && attr.value == "1")));
+}
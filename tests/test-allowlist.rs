@@ -0,0 +1,149 @@
+use crate::helpers::{assert_err, grant_authz, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::{AllowlistCapExceeded, NotOnAllowlist};
+use atomic_swaps_contract::msg::{AllowlistProof, ExecuteMsg, QueryMsg, TakerAllowlist};
+use cosmwasm_std::{coin, Uint128};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, Wasm};
+use sha2::{Digest, Sha256};
+
+pub mod helpers;
+
+fn leaf(address: &str, cap: u128) -> [u8; 32] {
+    Sha256::digest(format!("{address}{cap}").as_bytes()).into()
+}
+
+fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+#[test]
+fn allowlisted_takers_are_held_to_their_caps() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let outsider = &accs[3];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000, "uatom")),
+    );
+
+    // Two leaves: the taker may take 1.5 atom, the maker's own address 5 atom
+    let taker_leaf = leaf(&taker.address(), 1_500_000);
+    let other_leaf = leaf(&maker.address(), 5_000_000);
+    let merkle_root = hex::encode(parent(taker_leaf, other_leaf));
+    let taker_proof = AllowlistProof {
+        proof: vec![hex::encode(other_leaf)],
+        cap: Some(Uint128::new(1_500_000)),
+    };
+
+    for _ in 0..2 {
+        wasm.execute(
+            &contract_addr,
+            &ExecuteMsg::MakeOffer {
+                maker_coin: Coin::from(coin(1_000_000, "uatom")),
+                taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+                referral: None,
+                access_gate: None,
+                taker_allowlist: Some(TakerAllowlist {
+                    merkle_root: merkle_root.clone(),
+                    per_address_caps: true,
+                }),
+                holding_requirements: None,
+                proceeds_recipient: None,
+                payout_split: None,
+                proceeds_action: None,
+                approval_window: None,
+                reveal_delay: None,
+                bond: None,
+            },
+            &[],
+            maker,
+        )
+        .unwrap();
+    }
+
+    // Claiming the taker's leaf doesn't help another address
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::OfferTaker {
+                offer_id: "1".to_string(),
+                referral: None,
+                allowlist_proof: Some(taker_proof.clone()),
+                recipient: None,
+                callback: None,
+            },
+            &[coin(1_000_000, "uosmo")],
+            outsider,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOnAllowlist {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: Some(taker_proof.clone()),
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::OfferTaker {
+                offer_id: "2".to_string(),
+                referral: None,
+                allowlist_proof: Some(taker_proof),
+                recipient: None,
+                callback: None,
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        AllowlistCapExceeded {
+            cap: Uint128::new(1_500_000),
+            taken: Uint128::new(1_000_000),
+        },
+    );
+
+    let taken: Uint128 = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AllowlistTaken {
+                merkle_root,
+                address: taker.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(taken, Uint128::new(1_000_000));
+}
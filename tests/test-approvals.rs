@@ -0,0 +1,203 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::error::ContractError::{
+    NoApplicationFound, NotOfferMaker, OfferExpired,
+};
+use atomic_swaps_contract::msg::{Application, ExecuteMsg, MakerLimits, Offer, QueryMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+fn apply_msg() -> ExecuteMsg {
+    ExecuteMsg::from(TakeOffer::default())
+}
+
+#[test]
+fn makers_approve_one_applicant_and_the_rest_are_refunded() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let (accs, contract_addr) = setup_contract(&app, 5);
+    let maker = &accs[0];
+    let applicant = &accs[1];
+    let other_applicant = &accs[2];
+    let rejected_applicant = &accs[3];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer {
+            taker_coin: Coin::from(coin(2_000_000, "uosmo")),
+            approval_window: Some(60 * 60),
+            ..Default::default()
+        }),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    for taker in [applicant, other_applicant, rejected_applicant] {
+        wasm.execute(
+            &contract_addr,
+            &apply_msg(),
+            &[coin(2_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap();
+    }
+
+    // Applying escrows the taker coin without settling
+    let applications: Vec<Application> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Applicants {
+                offer_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(applications.len(), 3);
+    let offers: Vec<Offer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AllOffers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(offers[0].taker, None);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::RejectApplicant {
+                offer_id: "1".to_string(),
+                applicant: rejected_applicant.address(),
+            },
+            &[],
+            applicant,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOfferMaker {});
+
+    let rejected_balance = query_balance(&bank, &rejected_applicant.address(), "uosmo");
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::RejectApplicant {
+            offer_id: "1".to_string(),
+            applicant: rejected_applicant.address(),
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &rejected_applicant.address(), "uosmo"),
+        rejected_balance + 2_000_000
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::ApproveApplicant {
+                offer_id: "1".to_string(),
+                applicant: rejected_applicant.address(),
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(err_res, NoApplicationFound {});
+
+    let applicant_balance = query_balance(&bank, &applicant.address(), "uatom");
+    let other_balance = query_balance(&bank, &other_applicant.address(), "uosmo");
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::ApproveApplicant {
+            offer_id: "1".to_string(),
+            applicant: applicant.address(),
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_balance(&bank, &applicant.address(), "uatom"),
+        applicant_balance + 1_000_000
+    );
+    assert_eq!(
+        query_balance(&bank, &other_applicant.address(), "uosmo"),
+        other_balance + 2_000_000
+    );
+    let fulfilled: Offer = wasm
+        .query(&contract_addr, &QueryMsg::FulfilledOffers { offer_id: 1 })
+        .unwrap();
+    assert_eq!(fulfilled.taker, Some(applicant.address()));
+
+    let applications: Vec<Application> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Applicants {
+                offer_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(applications.is_empty());
+}
+
+#[test]
+fn expired_offers_cannot_be_approved() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let maker = &accs[0];
+    let applicant = &accs[1];
+    let admin = &accs[2];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateMakerLimits {
+            limits: MakerLimits {
+                offer_ttl: Some(100),
+                ..MakerLimits::default()
+            },
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+    // The approval window outlasts the offer
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer {
+            approval_window: Some(60 * 60),
+            ..Default::default()
+        }),
+        &[],
+        maker,
+    )
+    .unwrap();
+    wasm.execute(
+        &contract_addr,
+        &apply_msg(),
+        &[coin(1_000_000, "uosmo")],
+        applicant,
+    )
+    .unwrap();
+
+    app.increase_time(100);
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::ApproveApplicant {
+                offer_id: "1".to_string(),
+                applicant: applicant.address(),
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(err_res, OfferExpired {});
+}
@@ -0,0 +1,152 @@
+use crate::helpers::{assert_err, grant_authz, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::{BreakerTripped, NotOwner};
+use atomic_swaps_contract::msg::{BreakerStatus, ExecuteMsg, QueryMsg, VolumeLimit};
+use cosmwasm_std::{coin, Uint128};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContractResponse;
+use osmosis_test_tube::{Module, OsmosisTestApp, RunnerExecuteResult, SigningAccount, Wasm};
+
+pub mod helpers;
+
+#[allow(clippy::result_large_err)]
+fn make_and_take(
+    wasm: &Wasm<OsmosisTestApp>,
+    contract_addr: &str,
+    maker: &SigningAccount,
+    taker: &SigningAccount,
+    offer_id: &str,
+) -> RunnerExecuteResult<MsgExecuteContractResponse> {
+    wasm.execute(
+        contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+    wasm.execute(
+        contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: offer_id.to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+}
+
+#[test]
+fn breaker_trips_on_excess_volume_until_reset() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            3,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000, "uatom")),
+    );
+
+    let limit = VolumeLimit {
+        denom: "uatom".to_string(),
+        max_volume: Uint128::new(1_500_000),
+        window: 3600,
+    };
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateVolumeLimits {
+            limits: vec![limit.clone()],
+            remove: vec![],
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    make_and_take(&wasm, &contract_addr, maker, taker, "1").unwrap();
+    // The fill that exceeds the limit completes and trips the breaker
+    make_and_take(&wasm, &contract_addr, maker, taker, "2").unwrap();
+
+    let breakers: Vec<BreakerStatus> = wasm.query(&contract_addr, &QueryMsg::Breakers {}).unwrap();
+    assert_eq!(breakers.len(), 1);
+    assert_eq!(breakers[0].limit, limit);
+    assert_eq!(breakers[0].window_volume, Uint128::new(2_000_000));
+    assert!(breakers[0].tripped_at.is_some());
+
+    let err_res = make_and_take(&wasm, &contract_addr, maker, taker, "3").unwrap_err();
+    assert_err(
+        err_res,
+        BreakerTripped {
+            denom: "uatom".to_string(),
+        },
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::ResetBreaker {
+                denom: "uatom".to_string(),
+            },
+            &[],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwner {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::ResetBreaker {
+            denom: "uatom".to_string(),
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "3".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    let breakers: Vec<BreakerStatus> = wasm.query(&contract_addr, &QueryMsg::Breakers {}).unwrap();
+    assert_eq!(breakers[0].window_volume, Uint128::new(1_000_000));
+    assert_eq!(breakers[0].tripped_at, None);
+}
@@ -0,0 +1,82 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::error::ContractError::{
+    CallbackWithRecipient, InaccurateFunds, NoFlashInProgress,
+};
+use atomic_swaps_contract::msg::{ExecuteMsg, TakerCallback};
+use cosmwasm_std::{coin, to_binary};
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+fn take_offer_msg(flash: bool, recipient: Option<String>) -> ExecuteMsg {
+    ExecuteMsg::from(TakeOffer {
+        recipient,
+        callback: Some(TakerCallback {
+            msg: to_binary(&"swap").unwrap(),
+            flash,
+        }),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn flash_takes_have_to_be_repaid_by_the_taker() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer::default()),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    // The maker coin has to reach the taker the callback is sent to
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_offer_msg(false, Some(admin.address())),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, CallbackWithRecipient {});
+
+    // Flash takers pay during the callback, not upfront
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_offer_msg(true, None),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, InaccurateFunds {});
+
+    // A plain account can't receive the callback, so the whole take reverts
+    let maker_balance = query_balance(&bank, &maker.address(), "uatom");
+    wasm.execute(&contract_addr, &take_offer_msg(true, None), &[], taker)
+        .unwrap_err();
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uatom"),
+        maker_balance
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::RepayFlash {
+                offer_id: "1".to_string(),
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, NoFlashInProgress {});
+}
@@ -0,0 +1,343 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::commitments::{commitment_hash, REVEAL_WINDOW};
+use atomic_swaps_contract::error::ContractError::{
+    CommitRevealRequired, CommitmentExists, CommitmentExpired, CommitmentNotEarliest,
+    CommitmentNotMatured, CommitmentsDisabled, InaccurateFunds, NoCommitmentFound, NoOfferFound,
+};
+use atomic_swaps_contract::msg::{Commitment, CommitmentConfig, ExecuteMsg, Offer, QueryMsg};
+use cosmwasm_std::{coin, Addr};
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+pub mod helpers;
+
+fn commit_msg(taker: &SigningAccount, offer_id: &str, salt: &str) -> ExecuteMsg {
+    ExecuteMsg::CommitTake {
+        offer_id: offer_id.to_string(),
+        commitment: commitment_hash(offer_id, &Addr::unchecked(taker.address()), salt),
+    }
+}
+
+fn commit(
+    wasm: &Wasm<OsmosisTestApp>,
+    contract_addr: &str,
+    taker: &SigningAccount,
+    offer_id: &str,
+    salt: &str,
+) {
+    wasm.execute(
+        contract_addr,
+        &commit_msg(taker, offer_id, salt),
+        &[coin(1_000, "uion")],
+        taker,
+    )
+    .unwrap();
+}
+
+fn query_commitment(
+    wasm: &Wasm<OsmosisTestApp>,
+    contract_addr: &str,
+    taker: &SigningAccount,
+    salt: &str,
+) -> Commitment {
+    wasm.query::<_, Option<Commitment>>(
+        contract_addr,
+        &QueryMsg::Commitment {
+            commitment: commitment_hash("1", &Addr::unchecked(taker.address()), salt),
+        },
+    )
+    .unwrap()
+    .unwrap()
+}
+
+fn reveal_msg(salt: &str) -> ExecuteMsg {
+    ExecuteMsg::RevealTake {
+        offer_id: "1".to_string(),
+        salt: salt.to_string(),
+        referral: None,
+        allowlist_proof: None,
+        recipient: None,
+        callback: None,
+    }
+}
+
+fn make_offer_msg(reveal_delay: u64) -> ExecuteMsg {
+    ExecuteMsg::from(MakeOffer {
+        reveal_delay: Some(reveal_delay),
+        ..Default::default()
+    })
+}
+
+fn setup(app: &OsmosisTestApp) -> (Vec<SigningAccount>, String) {
+    let wasm = Wasm::new(app);
+    let (accs, contract_addr) = setup_contract(app, 4);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateCommitmentConfig {
+            config: Some(CommitmentConfig {
+                deposit: coin(1_000, "uion"),
+            }),
+        },
+        &[],
+        &accs[3],
+    )
+    .unwrap();
+
+    (accs, contract_addr)
+}
+
+#[test]
+fn the_earliest_committer_takes_the_offer() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup(&app);
+    let maker = &accs[0];
+    let first = &accs[1];
+    let second = &accs[2];
+
+    wasm.execute(&contract_addr, &make_offer_msg(1), &[], maker)
+        .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::from(TakeOffer::default()),
+            &[coin(1_000_000, "uosmo")],
+            first,
+        )
+        .unwrap_err();
+    assert_err(err_res, CommitRevealRequired {});
+
+    // Commitments to other offers don't hold up reveals
+    wasm.execute(&contract_addr, &make_offer_msg(1), &[], maker)
+        .unwrap();
+    commit(&wasm, &contract_addr, &accs[3], "2", "other");
+
+    let err_res = wasm
+        .execute(&contract_addr, &commit_msg(first, "1", "first"), &[], first)
+        .unwrap_err();
+    assert_err(err_res, InaccurateFunds {});
+    commit(&wasm, &contract_addr, first, "1", "first");
+    commit(&wasm, &contract_addr, second, "1", "second");
+
+    // The first commitment to the offer takes precedence
+    let first_commitment = query_commitment(&wasm, &contract_addr, first, "first");
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &reveal_msg("second"),
+            &[coin(1_000_000, "uosmo")],
+            second,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        CommitmentNotEarliest {
+            expires_at_height: first_commitment.expires_at_height,
+        },
+    );
+
+    let first_balance = query_balance(&bank, &first.address(), "uatom");
+    let first_deposit_balance = query_balance(&bank, &first.address(), "uion");
+    let second_deposit_balance = query_balance(&bank, &second.address(), "uion");
+    wasm.execute(
+        &contract_addr,
+        &reveal_msg("first"),
+        &[coin(1_000_000, "uosmo")],
+        first,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &first.address(), "uatom"),
+        first_balance + 1_000_000
+    );
+    let fulfilled: Offer = wasm
+        .query(&contract_addr, &QueryMsg::FulfilledOffers { offer_id: 1 })
+        .unwrap();
+    assert_eq!(fulfilled.taker, Some(first.address()));
+
+    // Deposits are returned on reveal, and to live commitments when the offer closes
+    assert_eq!(
+        query_balance(&bank, &first.address(), "uion"),
+        first_deposit_balance + 1_000
+    );
+    assert_eq!(
+        query_balance(&bank, &second.address(), "uion"),
+        second_deposit_balance + 1_000
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &reveal_msg("second"),
+            &[coin(1_000_000, "uosmo")],
+            second,
+        )
+        .unwrap_err();
+    // Commitments are dropped with the offer
+    assert_err(err_res, NoCommitmentFound {});
+}
+
+#[test]
+fn unrevealed_commitments_only_hold_up_later_ones_until_they_expire() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup(&app);
+    let maker = &accs[0];
+    let squatter = &accs[1];
+    let taker = &accs[2];
+
+    wasm.execute(&contract_addr, &make_offer_msg(2), &[], maker)
+        .unwrap();
+    commit(&wasm, &contract_addr, squatter, "1", "never revealed");
+    for _ in 0..3 {
+        app.increase_time(5);
+    }
+    commit(&wasm, &contract_addr, taker, "1", "taker");
+
+    let squatter_commitment = query_commitment(&wasm, &contract_addr, squatter, "never revealed");
+    assert_eq!(
+        squatter_commitment.expires_at_height,
+        squatter_commitment.committed_at_height + 2 + REVEAL_WINDOW
+    );
+    for _ in 0..3 {
+        app.increase_time(5);
+    }
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &reveal_msg("taker"),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        CommitmentNotEarliest {
+            expires_at_height: squatter_commitment.expires_at_height,
+        },
+    );
+
+    // The later commitment is still live once the earlier one has expired
+    while (app.get_block_height() as u64) < squatter_commitment.expires_at_height {
+        app.increase_time(5);
+    }
+    assert!(
+        (app.get_block_height() as u64)
+            < query_commitment(&wasm, &contract_addr, taker, "taker").expires_at_height
+    );
+
+    let maker_balance = query_balance(&bank, &maker.address(), "uion");
+    let taker_balance = query_balance(&bank, &taker.address(), "uatom");
+    wasm.execute(
+        &contract_addr,
+        &reveal_msg("taker"),
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &taker.address(), "uatom"),
+        taker_balance + 1_000_000
+    );
+    // The expired commitment's deposit is forfeited to the maker
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uion"),
+        maker_balance + 1_000
+    );
+    let squatter_commitment: Option<Commitment> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Commitment {
+                commitment: commitment_hash(
+                    "1",
+                    &Addr::unchecked(squatter.address()),
+                    "never revealed",
+                ),
+            },
+        )
+        .unwrap();
+    assert_eq!(squatter_commitment, None);
+}
+
+#[test]
+fn premature_and_stale_commitments_are_refused() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 4);
+    let maker = &accs[0];
+    let early = &accs[1];
+    let taker = &accs[2];
+    let admin = &accs[3];
+
+    // Reveal-only offers need commitments to be enabled
+    let err_res = wasm
+        .execute(&contract_addr, &make_offer_msg(3), &[], maker)
+        .unwrap_err();
+    assert_err(err_res, CommitmentsDisabled {});
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateCommitmentConfig {
+            config: Some(CommitmentConfig {
+                deposit: coin(1_000, "uion"),
+            }),
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    // Offer ids are predictable, but can't be committed to before the offer exists
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &commit_msg(early, "1", "early"),
+            &[coin(1_000, "uion")],
+            early,
+        )
+        .unwrap_err();
+    assert_err(err_res, NoOfferFound {});
+    wasm.execute(&contract_addr, &make_offer_msg(3), &[], maker)
+        .unwrap();
+
+    commit(&wasm, &contract_addr, taker, "1", "taker");
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &commit_msg(taker, "1", "again"),
+            &[coin(1_000, "uion")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, CommitmentExists {});
+    let commitment = query_commitment(&wasm, &contract_addr, taker, "taker");
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &reveal_msg("taker"),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        CommitmentNotMatured {
+            reveal_at_height: commitment.committed_at_height + 3,
+        },
+    );
+
+    while (app.get_block_height() as u64) < commitment.expires_at_height {
+        app.increase_time(5);
+    }
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &reveal_msg("taker"),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, CommitmentExpired {});
+}
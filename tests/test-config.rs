@@ -0,0 +1,133 @@
+use crate::helpers::{assert_err, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::{NotOwner, NotPendingOwner};
+use atomic_swaps_contract::msg::{ConfigResponse, ExecuteMsg, QueryMsg};
+use cosmwasm_std::coin;
+use cw2::ContractVersion;
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn ownership_is_transferred_in_two_steps() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 3)
+        .unwrap();
+    let owner = &accs[0];
+    let new_owner = &accs[1];
+    let user = &accs[2];
+
+    let contract_addr = instantiate_contract(&wasm, owner);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::ProposeNewOwner {
+                owner: user.address(),
+            },
+            &[],
+            user,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwner {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::ProposeNewOwner {
+            owner: new_owner.address(),
+        },
+        &[],
+        owner,
+    )
+    .unwrap();
+
+    // Only the proposed owner can accept, and the owner keeps control until they do
+    let err_res = wasm
+        .execute(&contract_addr, &ExecuteMsg::AcceptOwnership {}, &[], user)
+        .unwrap_err();
+    assert_err(err_res, NotPendingOwner {});
+
+    let config: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.owner, Some(owner.address()));
+    assert_eq!(config.pending_owner, Some(new_owner.address()));
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::AcceptOwnership {},
+        &[],
+        new_owner,
+    )
+    .unwrap();
+
+    let config: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.owner, Some(new_owner.address()));
+    assert_eq!(config.pending_owner, None);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::UpdateConfig {
+                max_query_limit: Some(50),
+            },
+            &[],
+            owner,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwner {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateConfig {
+            max_query_limit: Some(50),
+        },
+        &[],
+        new_owner,
+    )
+    .unwrap();
+
+    let config: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.max_query_limit, 50);
+}
+
+#[test]
+fn owner_renounces_ownership() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 1)
+        .unwrap();
+    let owner = &accs[0];
+
+    let contract_addr = instantiate_contract(&wasm, owner);
+
+    let info: ContractVersion = wasm
+        .query(&contract_addr, &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(info.contract, "atomic-swaps-contract");
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::RenounceOwnership {},
+        &[],
+        owner,
+    )
+    .unwrap();
+
+    let config: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.owner, None);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::UpdateConfig {
+                max_query_limit: Some(50),
+            },
+            &[],
+            owner,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwner {});
+}
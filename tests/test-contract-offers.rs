@@ -0,0 +1,392 @@
+use crate::helpers::{assert_err, jit_maker_wasm_file, query_balance, setup_contract};
+use atomic_swaps_contract::error::ContractError::{
+    DenomNotAllowed, InvalidContractOffer, NotMakerContract, QuoteSizeExceeded, SlippageExceeded,
+};
+use atomic_swaps_contract::msg::{
+    ContractOffer, ExecuteMsg, FeePayer, FillSettlement, MakerAsset, QueryMsg, Referral,
+    ReferralFeeSource,
+};
+use cosmwasm_std::{coin, Uint128};
+use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, SigningAccount, Wasm};
+use serde_json::json;
+
+pub mod helpers;
+
+fn instantiate_maker(
+    wasm: &Wasm<OsmosisTestApp>,
+    owner: &SigningAccount,
+    swap_contract: &str,
+) -> String {
+    let code_id = wasm
+        .store_code(&jit_maker_wasm_file(), None, owner)
+        .unwrap()
+        .data
+        .code_id;
+    wasm.instantiate(
+        code_id,
+        &json!({ "swap_contract": swap_contract, "price": "0.5", "max_fill": "1000000" }),
+        None,
+        Some("jit-maker"),
+        &[],
+        owner,
+    )
+    .unwrap()
+    .data
+    .address
+}
+
+fn take_msg(min_maker_amount: u128) -> ExecuteMsg {
+    ExecuteMsg::TakeContractOffer {
+        offer_id: "1".to_string(),
+        min_maker_amount: Uint128::new(min_maker_amount),
+        referral: None,
+    }
+}
+
+#[test]
+fn contract_offers_are_priced_by_the_maker_at_take_time() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let owner = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let maker_addr = instantiate_maker(&wasm, owner, &contract_addr);
+
+    let make_msg = ExecuteMsg::MakeContractOffer {
+        maker_asset: MakerAsset::Native {
+            denom: "uatom".to_string(),
+        },
+        taker_denom: "uosmo".to_string(),
+        settlement: FillSettlement::Escrow {},
+    };
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &make_msg,
+            &[coin(1_500_000, "uatom")],
+            owner,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotMakerContract {});
+
+    wasm.execute(
+        &maker_addr,
+        &json!({ "make_offer": {
+            "maker_asset": { "native": { "denom": "uatom" } },
+            "taker_denom": "uosmo",
+            "settlement": { "escrow": {} },
+        }}),
+        &[coin(1_500_000, "uatom")],
+        owner,
+    )
+    .unwrap();
+
+    // 0.5 uatom per uosmo quotes 500_000 uatom, short of the taker's limit
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_msg(600_000),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        SlippageExceeded {
+            maker_amount: Uint128::new(500_000),
+            min_maker_amount: Uint128::new(600_000),
+        },
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_msg(0),
+            &[coin(3_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        QuoteSizeExceeded {
+            max_maker_amount: Uint128::new(1_000_000),
+        },
+    );
+
+    // Contract offers follow the denom blocklist like regular offers
+    let update_denoms = |block: Vec<String>, unlist: Vec<String>| {
+        wasm.execute(
+            &contract_addr,
+            &ExecuteMsg::UpdateDenoms {
+                allow: vec![],
+                block,
+                unlist,
+            },
+            &[],
+            admin,
+        )
+        .unwrap();
+    };
+    update_denoms(vec!["uatom".to_string()], vec![]);
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_msg(500_000),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        DenomNotAllowed {
+            denom: "uatom".to_string(),
+        },
+    );
+    update_denoms(vec![], vec!["uatom".to_string()]);
+
+    let taker_balance = query_balance(&bank, &taker.address(), "uatom");
+    wasm.execute(
+        &contract_addr,
+        &take_msg(500_000),
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &taker.address(), "uatom"),
+        taker_balance + 500_000
+    );
+    assert_eq!(query_balance(&bank, &maker_addr, "uosmo"), 1_000_000);
+
+    let offer: Option<ContractOffer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::ContractOffer {
+                offer_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(offer.unwrap().escrow, Uint128::new(1_000_000));
+}
+
+#[test]
+fn maker_transfer_fills_are_paid_by_the_maker() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let owner = &accs[0];
+    let taker = &accs[1];
+    let maker_addr = instantiate_maker(&wasm, owner, &contract_addr);
+    wasm.execute(
+        &maker_addr,
+        &json!({ "make_offer": {
+            "maker_asset": { "native": { "denom": "uatom" } },
+            "taker_denom": "uosmo",
+            "settlement": { "maker_transfer": {} },
+        }}),
+        &[],
+        owner,
+    )
+    .unwrap();
+
+    // The maker holds no inventory yet, so it can't quote any size
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_msg(0),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        QuoteSizeExceeded {
+            max_maker_amount: Uint128::zero(),
+        },
+    );
+
+    bank.send(
+        MsgSend {
+            from_address: owner.address(),
+            to_address: maker_addr.clone(),
+            amount: vec![coin(800_000, "uatom").into()],
+        },
+        owner,
+    )
+    .unwrap();
+
+    let quote: Uint128 = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::ContractOfferQuote {
+                offer_id: "1".to_string(),
+                taker: taker.address(),
+                taker_coin: coin(1_000_000, "uosmo"),
+            },
+        )
+        .unwrap();
+    assert_eq!(quote, Uint128::new(500_000));
+
+    let taker_balance = query_balance(&bank, &taker.address(), "uatom");
+    wasm.execute(
+        &contract_addr,
+        &take_msg(500_000),
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &taker.address(), "uatom"),
+        taker_balance + 500_000
+    );
+    assert_eq!(query_balance(&bank, &maker_addr, "uatom"), 300_000);
+}
+
+#[test]
+fn takers_pay_fees_out_of_the_escrowed_maker_coin() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 4);
+    let owner = &accs[0];
+    let taker = &accs[1];
+    let referrer = &accs[2];
+    let admin = &accs[3];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateFees {
+            fee_bps: Some(100),
+            fee_recipient: None,
+            fee_payer: Some(FeePayer::Taker),
+            pair_fees: None,
+            max_referral_fee_bps: Some(50),
+            referral_fee_source: Some(ReferralFeeSource::Proceeds),
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    let escrow_maker = instantiate_maker(&wasm, owner, &contract_addr);
+    wasm.execute(
+        &escrow_maker,
+        &json!({ "make_offer": {
+            "maker_asset": { "native": { "denom": "uatom" } },
+            "taker_denom": "uosmo",
+            "settlement": { "escrow": {} },
+        }}),
+        &[coin(1_500_000, "uatom")],
+        owner,
+    )
+    .unwrap();
+    let transfer_maker = instantiate_maker(&wasm, owner, &contract_addr);
+    wasm.execute(
+        &transfer_maker,
+        &json!({ "make_offer": {
+            "maker_asset": { "native": { "denom": "uatom" } },
+            "taker_denom": "uosmo",
+            "settlement": { "maker_transfer": {} },
+        }}),
+        &[],
+        owner,
+    )
+    .unwrap();
+
+    // The contract never holds the maker coin of maker transfer offers to take fees from
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::TakeContractOffer {
+                offer_id: "2".to_string(),
+                min_maker_amount: Uint128::zero(),
+                referral: None,
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        InvalidContractOffer {
+            reason: "maker transfer offers can't be taken while takers pay fees".to_string(),
+        },
+    );
+
+    // 1% protocol fee and a 0.5% referral fee on top, out of the 500_000 uatom quoted
+    let take_with_referral = ExecuteMsg::TakeContractOffer {
+        offer_id: "1".to_string(),
+        min_maker_amount: Uint128::new(500_000),
+        referral: Some(Referral {
+            address: referrer.address(),
+            fee_bps: 50,
+        }),
+    };
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_with_referral,
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        SlippageExceeded {
+            maker_amount: Uint128::new(492_500),
+            min_maker_amount: Uint128::new(500_000),
+        },
+    );
+
+    let taker_balance = query_balance(&bank, &taker.address(), "uatom");
+    let fee_balance = query_balance(&bank, &admin.address(), "uatom");
+    let referrer_balance = query_balance(&bank, &referrer.address(), "uatom");
+    let res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::TakeContractOffer {
+                offer_id: "1".to_string(),
+                min_maker_amount: Uint128::new(492_500),
+                referral: Some(Referral {
+                    address: referrer.address(),
+                    fee_bps: 50,
+                }),
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap();
+    assert_eq!(
+        query_balance(&bank, &taker.address(), "uatom"),
+        taker_balance + 492_500
+    );
+    assert_eq!(
+        query_balance(&bank, &admin.address(), "uatom"),
+        fee_balance + 5_000
+    );
+    assert_eq!(
+        query_balance(&bank, &referrer.address(), "uatom"),
+        referrer_balance + 2_500
+    );
+    assert_eq!(query_balance(&bank, &escrow_maker, "uosmo"), 1_000_000);
+
+    // Fills carry the same fee and referral attributes as regular settlements
+    let attrs = res
+        .events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+        .collect::<Vec<_>>();
+    for expected in [
+        ("fee", "5000uatom"),
+        ("fee_payer", "taker"),
+        ("referrer", referrer.address().as_str()),
+        ("referral_fee", "2500uatom"),
+    ] {
+        assert!(attrs.contains(&expected), "missing {expected:?}");
+    }
+}
@@ -0,0 +1,131 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer};
+use atomic_swaps_contract::error::ContractError::{NotCounterProposer, NotOfferMaker};
+use atomic_swaps_contract::msg::{Counter, ExecuteMsg, Offer, QueryMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+fn counter_msg(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Counter {
+        offer_id: "1".to_string(),
+        taker_coin: Coin::from(coin(amount, "uosmo")),
+    }
+}
+
+#[test]
+fn makers_settle_accepted_counters() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let (accs, contract_addr) = setup_contract(&app, 4);
+    let maker = &accs[0];
+    let proposer = &accs[1];
+    let other_proposer = &accs[2];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer {
+            taker_coin: Coin::from(coin(2_000_000, "uosmo")),
+            ..Default::default()
+        }),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &counter_msg(1_800_000),
+        &[coin(1_800_000, "uosmo")],
+        proposer,
+    )
+    .unwrap();
+    wasm.execute(
+        &contract_addr,
+        &counter_msg(1_500_000),
+        &[coin(1_500_000, "uosmo")],
+        other_proposer,
+    )
+    .unwrap();
+
+    let counters: Vec<Counter> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::CountersByOffer {
+                offer_id: "1".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        counters
+            .iter()
+            .map(|counter| counter.proposer.clone())
+            .collect::<Vec<_>>(),
+        vec![proposer.address(), other_proposer.address()]
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::AcceptCounter { counter_id: 1 },
+            &[],
+            proposer,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOfferMaker {});
+
+    let proposer_balance = query_balance(&bank, &proposer.address(), "uatom");
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::AcceptCounter { counter_id: 1 },
+        &[],
+        maker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &proposer.address(), "uatom"),
+        proposer_balance + 1_000_000
+    );
+    let fulfilled: Offer = wasm
+        .query(&contract_addr, &QueryMsg::FulfilledOffers { offer_id: 1 })
+        .unwrap();
+    assert_eq!(fulfilled.taker, Some(proposer.address()));
+    assert_eq!(fulfilled.taker_coin, Coin::from(coin(1_800_000, "uosmo")));
+
+    // The other counter stays escrowed until its proposer withdraws it
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::WithdrawCounter { counter_id: 2 },
+            &[],
+            proposer,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotCounterProposer {});
+
+    let other_balance = query_balance(&bank, &other_proposer.address(), "uosmo");
+    let counters: Vec<Counter> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::CountersByProposer {
+                proposer: other_proposer.address(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(counters.len(), 1);
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::WithdrawCounter { counter_id: 2 },
+        &[],
+        other_proposer,
+    )
+    .unwrap();
+    assert!(query_balance(&bank, &other_proposer.address(), "uosmo") > other_balance);
+}
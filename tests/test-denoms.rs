@@ -0,0 +1,135 @@
+use crate::helpers::{assert_err, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::UnknownIbcDenom;
+use atomic_swaps_contract::msg::{
+    DenomInfo, ExecuteMsg, IbcChannelsResponse, OfferWithDenoms, QueryMsg,
+};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+const ATOM_IBC_DENOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+#[test]
+fn untraceable_ibc_denom_is_rejected_when_approval_required() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let maker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateIbcChannels {
+            require_approved: Some(true),
+            approve: vec!["channel-0".to_string()],
+            revoke: vec![],
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    let channels: IbcChannelsResponse = wasm
+        .query(&contract_addr, &QueryMsg::IbcChannels {})
+        .unwrap();
+    assert_eq!(
+        channels,
+        IbcChannelsResponse {
+            require_approved: true,
+            approved: vec!["channel-0".to_string()],
+        }
+    );
+
+    // No transfer ever brought this denom to the test chain, so it has no trace
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::MakeOffer {
+                maker_coin: Coin::from(coin(1_000_000, ATOM_IBC_DENOM)),
+                taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+                referral: None,
+                access_gate: None,
+                taker_allowlist: None,
+                holding_requirements: None,
+                proceeds_recipient: None,
+                payout_split: None,
+                proceeds_action: None,
+                approval_window: None,
+                reveal_delay: None,
+                bond: None,
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        UnknownIbcDenom {
+            denom: ATOM_IBC_DENOM.to_string(),
+        },
+    );
+}
+
+#[test]
+fn offers_are_returned_with_denom_info() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let maker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let offers: Vec<OfferWithDenoms> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::OffersWithDenoms {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(offers.len(), 1);
+    assert_eq!(offers[0].offer_id, "1");
+    assert_eq!(
+        offers[0].maker_denom,
+        DenomInfo {
+            denom: "uatom".to_string(),
+            base_denom: "uatom".to_string(),
+            path: "".to_string(),
+            display: None,
+            decimals: None,
+        }
+    );
+}
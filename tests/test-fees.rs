@@ -0,0 +1,287 @@
+use crate::helpers::{
+    assert_err, default_instantiate_msg, grant_authz, instantiate_contract,
+    instantiate_contract_with_msg, query_balance,
+};
+use atomic_swaps_contract::error::ContractError::{FeeTooHigh, NotOwner};
+use atomic_swaps_contract::msg::{
+    ExecuteMsg, FeePayer, FeesResponse, InstantiateMsg, Offer, PairFee, QueryMsg, ReferralFeeSource,
+};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn maker_pays_protocol_fee() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let treasury = &accs[3];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            fee_bps: 100,
+            fee_recipient: treasury.address(),
+            fee_payer: FeePayer::Maker,
+            ..default_instantiate_msg(admin)
+        },
+    );
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    // 1% of the taker coin goes to the treasury, the taker receives the full maker coin
+    let treasury_osmo_balance = query_balance(&bank, &treasury.address(), "uosmo");
+    let taker_atom_balance = query_balance(&bank, &taker.address(), "uatom");
+    assert_eq!(treasury_osmo_balance, 1_000_010_000_000);
+    assert_eq!(taker_atom_balance, 1_001_000_000_000);
+
+    let response: Offer = wasm
+        .query(&contract_addr, &QueryMsg::FulfilledOffers { offer_id: 1 })
+        .unwrap();
+    assert_eq!(response.fee, Some(Coin::from(coin(10_000_000, "uosmo"))));
+}
+
+#[test]
+fn taker_pays_pair_fee_override() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let treasury = &accs[3];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            fee_bps: 100,
+            fee_recipient: treasury.address(),
+            fee_payer: FeePayer::Taker,
+            pair_fees: vec![PairFee {
+                maker_denom: "uatom".to_string(),
+                taker_denom: "uosmo".to_string(),
+                fee_bps: 50,
+            }],
+            ..default_instantiate_msg(admin)
+        },
+    );
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    // 0.5% of the maker coin goes to the treasury instead of the taker
+    let treasury_atom_balance = query_balance(&bank, &treasury.address(), "uatom");
+    let taker_atom_balance = query_balance(&bank, &taker.address(), "uatom");
+    let maker_atom_balance = query_balance(&bank, &maker.address(), "uatom");
+    assert_eq!(treasury_atom_balance, 1_000_005_000_000);
+    assert_eq!(taker_atom_balance, 1_000_995_000_000);
+    assert_eq!(maker_atom_balance, 999_000_000_000);
+}
+
+#[test]
+fn non_admin_attempts_to_update_fees() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let user = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::UpdateFees {
+                fee_bps: Some(100),
+                fee_recipient: Some(user.address()),
+                fee_payer: None,
+                pair_fees: None,
+                max_referral_fee_bps: None,
+                referral_fee_source: None,
+            },
+            &[],
+            user,
+        )
+        .unwrap_err();
+
+    assert_err(err_res, NotOwner {})
+}
+
+#[test]
+fn admin_updates_fees_within_bounds() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let treasury = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::UpdateFees {
+                fee_bps: Some(1_001),
+                fee_recipient: None,
+                fee_payer: None,
+                pair_fees: None,
+                max_referral_fee_bps: None,
+                referral_fee_source: None,
+            },
+            &[],
+            admin,
+        )
+        .unwrap_err();
+
+    assert_err(
+        err_res,
+        FeeTooHigh {
+            fee_bps: 1_001,
+            max_fee_bps: 1_000,
+        },
+    );
+
+    let pair_fees = vec![PairFee {
+        maker_denom: "uatom".to_string(),
+        taker_denom: "uosmo".to_string(),
+        fee_bps: 25,
+    }];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateFees {
+            fee_bps: Some(30),
+            fee_recipient: Some(treasury.address()),
+            fee_payer: Some(FeePayer::Taker),
+            pair_fees: Some(pair_fees.clone()),
+            max_referral_fee_bps: None,
+            referral_fee_source: None,
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    let fees: FeesResponse = wasm.query(&contract_addr, &QueryMsg::Fees {}).unwrap();
+    assert_eq!(
+        fees,
+        FeesResponse {
+            fee_bps: 30,
+            fee_recipient: treasury.address(),
+            fee_payer: FeePayer::Taker,
+            pair_fees,
+            max_referral_fee_bps: 0,
+            referral_fee_source: ReferralFeeSource::ProtocolFee,
+        }
+    );
+}
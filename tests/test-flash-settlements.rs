@@ -0,0 +1,172 @@
+use crate::helpers::{MakeOffer, TakeOffer};
+use atomic_swaps_contract::actions::PROCEEDS_ACTION_REPLY_ID;
+use atomic_swaps_contract::callbacks::FLASH_CALLBACK_REPLY_ID;
+use atomic_swaps_contract::contract::{execute, instantiate, reply};
+use atomic_swaps_contract::error::ContractError::FlashInProgress;
+use atomic_swaps_contract::msg::{
+    ExecuteMsg, FeePayer, InstantiateMsg, ProceedsAction, ReferralFeeSource, TakerCallback,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coin, to_binary, Reply, SubMsgResponse, SubMsgResult};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+
+pub mod helpers;
+
+// A flash taker's contract could settle other offers from within its callback, while the flash
+// take's own payouts are still waiting on it. The callback is played out here by calling the
+// entry points the taker contract would.
+
+#[test]
+fn offers_cannot_settle_from_within_a_flash_callback() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let contract = env.contract.address.to_string();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            guardian: None,
+            max_query_limit: 30,
+            access_gate: None,
+            fee_bps: 0,
+            fee_recipient: "owner".to_string(),
+            fee_payer: FeePayer::Maker,
+            pair_fees: vec![],
+            staking_token: None,
+            max_referral_fee_bps: 0,
+            referral_fee_source: ReferralFeeSource::ProtocolFee,
+        },
+    )
+    .unwrap();
+
+    // The flash taker made an approval offer with a proceeds action, and applied to it
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("flasher", &[]),
+        MakeOffer {
+            taker_coin: Coin::from(coin(2_000_000, "uosmo")),
+            approval_window: Some(60 * 60),
+            proceeds_action: Some(ProceedsAction::Delegate {
+                validator: "validator".to_string(),
+            }),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("applicant", &[coin(2_000_000, "uosmo")]),
+        TakeOffer::default().into(),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("maker", &[]),
+        MakeOffer::default().into(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("flasher", &[]),
+        TakeOffer {
+            offer_id: "2".to_string(),
+            callback: Some(TakerCallback {
+                msg: to_binary(&"swap").unwrap(),
+                flash: true,
+            }),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&contract, &[]),
+        ExecuteMsg::FulfillOffer {
+            offer_id: "2".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(res
+        .messages
+        .iter()
+        .any(|sub_msg| sub_msg.id == FLASH_CALLBACK_REPLY_ID));
+
+    // Within the callback
+    let approve = ExecuteMsg::ApproveApplicant {
+        offer_id: "1".to_string(),
+        applicant: "applicant".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("flasher", &[]),
+        approve.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, FlashInProgress {});
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&contract, &[coin(2_000_000, "uosmo")]),
+        ExecuteMsg::FulfillOffer {
+            offer_id: "1".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, FlashInProgress {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("flasher", &[coin(1_000_000, "uosmo")]),
+        ExecuteMsg::RepayFlash {
+            offer_id: "2".to_string(),
+        },
+    )
+    .unwrap();
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: FLASH_CALLBACK_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    // Once the flash take has settled, the approval goes through with its proceeds action
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("flasher", &[]),
+        approve,
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&contract, &[coin(2_000_000, "uosmo")]),
+        ExecuteMsg::FulfillOffer {
+            offer_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(res
+        .messages
+        .iter()
+        .any(|sub_msg| sub_msg.id == PROCEEDS_ACTION_REPLY_ID));
+}
@@ -0,0 +1,223 @@
+use crate::helpers::{
+    assert_err, cw4_group_wasm_file, default_instantiate_msg, grant_authz,
+    instantiate_contract_with_msg,
+};
+use atomic_swaps_contract::error::ContractError::NotAuthorizedParticipant;
+use atomic_swaps_contract::msg::{AccessGate, ExecuteMsg, InstantiateMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, SigningAccount, Wasm};
+use serde_json::json;
+
+pub mod helpers;
+
+fn instantiate_group(
+    wasm: &Wasm<OsmosisTestApp>,
+    admin: &SigningAccount,
+    members: &[&SigningAccount],
+) -> String {
+    let code_id = wasm
+        .store_code(&cw4_group_wasm_file(), None, admin)
+        .unwrap()
+        .data
+        .code_id;
+    let members = members
+        .iter()
+        .map(|member| json!({ "addr": member.address(), "weight": 1 }))
+        .collect::<Vec<_>>();
+
+    wasm.instantiate(
+        code_id,
+        &json!({ "admin": admin.address(), "members": members }),
+        None,
+        Some("cw4-group"),
+        &[],
+        admin,
+    )
+    .unwrap()
+    .data
+    .address
+}
+
+fn make_offer_msg(access_gate: Option<AccessGate>) -> ExecuteMsg {
+    ExecuteMsg::MakeOffer {
+        maker_coin: Coin::from(coin(1_000_000, "uatom")),
+        taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+        referral: None,
+        access_gate,
+        taker_allowlist: None,
+        holding_requirements: None,
+        proceeds_recipient: None,
+        payout_split: None,
+        proceeds_action: None,
+        approval_window: None,
+        reveal_delay: None,
+        bond: None,
+    }
+}
+
+fn take_offer_msg() -> ExecuteMsg {
+    ExecuteMsg::OfferTaker {
+        offer_id: "1".to_string(),
+        referral: None,
+        allowlist_proof: None,
+        recipient: None,
+        callback: None,
+    }
+}
+
+#[test]
+fn global_gate_admits_only_group_members() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let outsider = &accs[3];
+
+    let group_addr = instantiate_group(&wasm, admin, &[maker]);
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            access_gate: Some(AccessGate::Cw4Group {
+                contract: group_addr.clone(),
+            }),
+            ..default_instantiate_msg(admin)
+        },
+    );
+    for account in [maker, outsider] {
+        grant_authz(
+            &app,
+            account,
+            &contract_addr,
+            Coin::from(coin(1_000_000_000_000, "uatom")),
+        );
+    }
+
+    let err_res = wasm
+        .execute(&contract_addr, &make_offer_msg(None), &[], outsider)
+        .unwrap_err();
+    assert_err(
+        err_res,
+        NotAuthorizedParticipant {
+            participant: outsider.address(),
+            gate: group_addr.clone(),
+        },
+    );
+
+    wasm.execute(&contract_addr, &make_offer_msg(None), &[], maker)
+        .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_offer_msg(),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        NotAuthorizedParticipant {
+            participant: taker.address(),
+            gate: group_addr.clone(),
+        },
+    );
+
+    wasm.execute(
+        &group_addr,
+        &json!({
+            "update_members": {
+                "add": [{ "addr": taker.address(), "weight": 1 }],
+                "remove": [],
+            }
+        }),
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &take_offer_msg(),
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+}
+
+#[test]
+fn offer_gate_applies_to_its_takers() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let member = &accs[1];
+    let admin = &accs[2];
+    let outsider = &accs[3];
+
+    let group_addr = instantiate_group(&wasm, admin, &[member]);
+    let contract_addr =
+        instantiate_contract_with_msg(&wasm, admin, &default_instantiate_msg(admin));
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000, "uatom")),
+    );
+
+    // The maker isn't a member, the offer's gate only applies to takers
+    wasm.execute(
+        &contract_addr,
+        &make_offer_msg(Some(AccessGate::Cw4Group {
+            contract: group_addr.clone(),
+        })),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_offer_msg(),
+            &[coin(1_000_000, "uosmo")],
+            outsider,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        NotAuthorizedParticipant {
+            participant: outsider.address(),
+            gate: group_addr,
+        },
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &take_offer_msg(),
+        &[coin(1_000_000, "uosmo")],
+        member,
+    )
+    .unwrap();
+}
@@ -0,0 +1,198 @@
+use crate::helpers::{assert_err, governance_sudo, grant_authz, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::{DenomDelisted, NotOwner};
+use atomic_swaps_contract::msg::{
+    AuditEntry, ConfigResponse, ExecuteMsg, Offer, Operation, Pause, QueryMsg, SudoMsg,
+};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn governance_cancels_offers_and_delists_denoms() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            2,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    governance_sudo(
+        &app,
+        &contract_addr,
+        &SudoMsg::ForceCancelOffer {
+            offer_id: "1".to_string(),
+        },
+        admin,
+    );
+    governance_sudo(
+        &app,
+        &contract_addr,
+        &SudoMsg::DelistDenom {
+            denom: "uatom".to_string(),
+        },
+        admin,
+    );
+
+    let offers: Vec<Offer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AllOffers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(offers, vec![]);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::MakeOffer {
+                maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+                taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+                referral: None,
+                access_gate: None,
+                taker_allowlist: None,
+                holding_requirements: None,
+                proceeds_recipient: None,
+                payout_split: None,
+                proceeds_action: None,
+                approval_window: None,
+                reveal_delay: None,
+                bond: None,
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        DenomDelisted {
+            denom: "uatom".to_string(),
+        },
+    );
+
+    let log: Vec<AuditEntry> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AuditLog {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let events: Vec<_> = log.iter().map(|entry| entry.event.ty.as_str()).collect();
+    assert_eq!(
+        events,
+        vec!["governance_force_cancel_offer", "governance_delist_denom"]
+    );
+}
+
+#[test]
+fn governance_pauses_and_replaces_owner() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let admin = &accs[0];
+    let new_owner = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    governance_sudo(
+        &app,
+        &contract_addr,
+        &SudoMsg::SetPause {
+            operation: Operation::Settle,
+            denom: None,
+            paused: true,
+        },
+        admin,
+    );
+    governance_sudo(
+        &app,
+        &contract_addr,
+        &SudoMsg::ReplaceOwner {
+            owner: new_owner.address(),
+        },
+        admin,
+    );
+
+    let pauses: Vec<Pause> = wasm.query(&contract_addr, &QueryMsg::Pauses {}).unwrap();
+    assert_eq!(
+        pauses,
+        vec![Pause {
+            operation: Operation::Settle,
+            denom: None,
+        }]
+    );
+
+    let config: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.owner, Some(new_owner.address()));
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::UpdateConfig {
+                max_query_limit: Some(50),
+            },
+            &[],
+            admin,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwner {});
+
+    let log: Vec<AuditEntry> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AuditLog {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].id, 2);
+    assert_eq!(log[0].event.ty, "governance_replace_owner");
+}
@@ -0,0 +1,148 @@
+use crate::helpers::{
+    assert_err, cw20_base_wasm_file, cw721_base_wasm_file, setup_contract, MakeOffer, TakeOffer,
+};
+use atomic_swaps_contract::error::ContractError::HoldingRequirementNotMet;
+use atomic_swaps_contract::msg::{ExecuteMsg, HoldingRequirement};
+use cosmwasm_std::{coin, Uint128};
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, SigningAccount, Wasm};
+use serde_json::{json, Value};
+
+pub mod helpers;
+
+fn instantiate(
+    wasm: &Wasm<OsmosisTestApp>,
+    admin: &SigningAccount,
+    wasm_file: &[u8],
+    msg: &Value,
+) -> String {
+    let code_id = wasm
+        .store_code(wasm_file, None, admin)
+        .unwrap()
+        .data
+        .code_id;
+    wasm.instantiate(code_id, msg, None, Some("holding"), &[], admin)
+        .unwrap()
+        .data
+        .address
+}
+
+// Makes an offer with `requirement`, which only `holder` meets
+fn assert_only_holders_take(
+    app: &OsmosisTestApp,
+    contract_addr: &str,
+    maker: &SigningAccount,
+    holder: &SigningAccount,
+    requirement: HoldingRequirement,
+) {
+    let wasm = Wasm::new(app);
+    let non_holder = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo")])
+        .unwrap();
+
+    let make_msg: ExecuteMsg = MakeOffer {
+        holding_requirements: Some(vec![requirement.clone()]),
+        ..Default::default()
+    }
+    .into();
+    wasm.execute(contract_addr, &make_msg, &[], maker).unwrap();
+
+    let take_msg: ExecuteMsg = TakeOffer::default().into();
+    let err_res = wasm
+        .execute(
+            contract_addr,
+            &take_msg,
+            &[coin(1_000_000, "uosmo")],
+            &non_holder,
+        )
+        .unwrap_err();
+    // The error names the requirement that wasn't met
+    assert_err(err_res, HoldingRequirementNotMet { requirement });
+
+    wasm.execute(
+        contract_addr,
+        &take_msg,
+        &[coin(1_000_000, "uosmo")],
+        holder,
+    )
+    .unwrap();
+}
+
+#[test]
+fn takers_have_to_hold_enough_of_a_native_denom() {
+    let app = OsmosisTestApp::new();
+    let (accs, contract_addr) = setup_contract(&app, 3);
+
+    assert_only_holders_take(
+        &app,
+        &contract_addr,
+        &accs[0],
+        &accs[1],
+        HoldingRequirement::NativeBalance {
+            denom: "uion".to_string(),
+            min_amount: Uint128::new(1_000),
+        },
+    );
+}
+
+#[test]
+fn takers_have_to_hold_enough_of_a_cw20_token() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let holder = &accs[1];
+    let admin = &accs[2];
+
+    let token = instantiate(
+        &wasm,
+        admin,
+        &cw20_base_wasm_file(),
+        &json!({
+            "name": "Holding",
+            "symbol": "HOLD",
+            "decimals": 6,
+            "initial_balances": [{ "address": holder.address(), "amount": "1000" }],
+        }),
+    );
+    assert_only_holders_take(
+        &app,
+        &contract_addr,
+        &accs[0],
+        holder,
+        HoldingRequirement::Cw20Balance {
+            contract: token,
+            min_amount: Uint128::new(1_000),
+        },
+    );
+}
+
+#[test]
+fn takers_have_to_own_a_token_of_a_cw721_collection() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let holder = &accs[1];
+    let admin = &accs[2];
+
+    let collection = instantiate(
+        &wasm,
+        admin,
+        &cw721_base_wasm_file(),
+        &json!({ "name": "Holding", "symbol": "HOLD", "minter": admin.address() }),
+    );
+    wasm.execute(
+        &collection,
+        &json!({ "mint": { "token_id": "1", "owner": holder.address() } }),
+        &[],
+        admin,
+    )
+    .unwrap();
+    assert_only_holders_take(
+        &app,
+        &contract_addr,
+        &accs[0],
+        holder,
+        HoldingRequirement::Cw721Owner {
+            contract: collection,
+        },
+    );
+}
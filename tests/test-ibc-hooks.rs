@@ -0,0 +1,183 @@
+use crate::helpers::{assert_err, grant_authz, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::{
+    InaccurateFunds, InvalidIbcChannel, NoIbcTransferFound,
+};
+use atomic_swaps_contract::msg::{ExecuteMsg, IbcTransfer, QueryMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+// ibc-hooks executes the contract from an intermediate sender account holding the transferred
+// funds, so a regular account executing IbcOfferTaker stands in for the hook here
+
+#[test]
+fn ibc_taker_provides_invalid_channel() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            3,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let hook_sender = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::IbcOfferTaker {
+                offer_id: "1".to_string(),
+                channel: "transfer/channel-0".to_string(),
+                receiver: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+                recovery_addr: None,
+            },
+            &[coin(1_000_000_000, "uosmo")],
+            hook_sender,
+        )
+        .unwrap_err();
+
+    assert_err(
+        err_res,
+        InvalidIbcChannel {
+            channel: "transfer/channel-0".to_string(),
+        },
+    )
+}
+
+#[test]
+fn ibc_taker_sends_incorrect_funds() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            3,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let hook_sender = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::IbcOfferTaker {
+                offer_id: "1".to_string(),
+                channel: "channel-0".to_string(),
+                receiver: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+                recovery_addr: Some(hook_sender.address()),
+            },
+            &[coin(1_000_000, "uosmo")],
+            hook_sender,
+        )
+        .unwrap_err();
+
+    assert_err(err_res, InaccurateFunds {})
+}
+
+#[test]
+fn retry_without_failed_ibc_transfer() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let caller = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    let transfer: Option<IbcTransfer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::IbcTransfer {
+                offer_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(transfer, None);
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::RetryIbcTransfer {
+                offer_id: "1".to_string(),
+            },
+            &[],
+            caller,
+        )
+        .unwrap_err();
+
+    assert_err(err_res, NoIbcTransferFound {})
+}
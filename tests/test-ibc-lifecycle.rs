@@ -0,0 +1,238 @@
+use atomic_swaps_contract::contract::{execute, instantiate, query, reply, sudo};
+use atomic_swaps_contract::error::ContractError::NoIbcTransferFound;
+use atomic_swaps_contract::ibc::{
+    MsgTransferResponse, IBC_TRANSFER_REPLY_ID, MAX_IBC_TRANSFER_ATTEMPTS,
+};
+use atomic_swaps_contract::msg::{
+    ExecuteMsg, FeePayer, IBCLifecycleComplete, IbcTransfer, IbcTransferStatus, InstantiateMsg,
+    QueryMsg, ReferralFeeSource, SudoMsg,
+};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coin, from_binary, BankMsg, Binary, CosmosMsg, Env, OwnedDeps, Reply, Response, SubMsgResponse,
+    SubMsgResult,
+};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use prost::Message;
+
+// The return transfer of an IBC take can only be followed up on a chain with a counterparty, so
+// its lifecycle is driven here by calling the entry points with the replies and sudo callbacks
+// the chain would send
+
+type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+const CHANNEL: &str = "channel-0";
+
+fn query_transfer(deps: &MockDeps, env: &Env) -> Option<IbcTransfer> {
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::IbcTransfer {
+            offer_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+// Answers the MsgTransfer sent in `res` with the sequence the chain assigned it
+fn reply_with_sequence(deps: &mut MockDeps, env: &Env, res: &Response, sequence: u64) {
+    assert!(res.messages.iter().any(|sub_msg| {
+        sub_msg.id == IBC_TRANSFER_REPLY_ID
+            && matches!(&sub_msg.msg, CosmosMsg::Stargate { type_url, .. }
+                if type_url == "/ibc.applications.transfer.v1.MsgTransfer")
+    }));
+
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: IBC_TRANSFER_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(
+                    MsgTransferResponse { sequence }.encode_to_vec(),
+                )),
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_transfer(deps, env).unwrap().status,
+        IbcTransferStatus::Pending { sequence }
+    );
+}
+
+fn timeout(deps: &mut MockDeps, env: &Env, sequence: u64) -> Response {
+    sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+            channel: CHANNEL.to_string(),
+            sequence,
+        }),
+    )
+    .unwrap()
+}
+
+// Settles an IBC take of 1_000_000 uatom, leaving its return transfer pending as sequence 1
+fn setup(recovery_addr: Option<&str>) -> (MockDeps, Env) {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            guardian: None,
+            max_query_limit: 30,
+            access_gate: None,
+            fee_bps: 0,
+            fee_recipient: "owner".to_string(),
+            fee_payer: FeePayer::Maker,
+            pair_fees: vec![],
+            staking_token: None,
+            max_referral_fee_bps: 0,
+            referral_fee_source: ReferralFeeSource::ProtocolFee,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("maker", &[]),
+        ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("hook", &[coin(1_000_000, "uosmo")]),
+        ExecuteMsg::IbcOfferTaker {
+            offer_id: "1".to_string(),
+            channel: CHANNEL.to_string(),
+            receiver: "cosmos1receiver".to_string(),
+            recovery_addr: recovery_addr.map(str::to_string),
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(env.contract.address.as_str(), &[coin(1_000_000, "uosmo")]),
+        ExecuteMsg::FulfillOffer {
+            offer_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+
+    let transfer = query_transfer(&deps, &env).unwrap();
+    assert_eq!(transfer.coin, Coin::from(coin(1_000_000, "uatom")));
+    assert_eq!(transfer.attempts, 1);
+    reply_with_sequence(&mut deps, &env, &res, 1);
+
+    (deps, env)
+}
+
+#[test]
+fn acknowledged_transfers_complete_the_ibc_take() {
+    let (mut deps, env) = setup(None);
+
+    // Acks are matched by channel and sequence
+    let err = sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+            channel: CHANNEL.to_string(),
+            sequence: 2,
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, NoIbcTransferFound {});
+
+    sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
+            channel: CHANNEL.to_string(),
+            sequence: 1,
+            ack: String::new(),
+            success: true,
+        }),
+    )
+    .unwrap();
+    assert_eq!(query_transfer(&deps, &env), None);
+}
+
+#[test]
+fn failed_transfers_are_retried_then_refunded() {
+    let (mut deps, env) = setup(Some("recovery"));
+
+    for sequence in 1..MAX_IBC_TRANSFER_ATTEMPTS as u64 {
+        let res = timeout(&mut deps, &env, sequence);
+        reply_with_sequence(&mut deps, &env, &res, sequence + 1);
+        assert_eq!(
+            query_transfer(&deps, &env).unwrap().attempts as u64,
+            sequence + 1
+        );
+    }
+
+    let res = timeout(&mut deps, &env, MAX_IBC_TRANSFER_ATTEMPTS as u64);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recovery".to_string(),
+            amount: vec![coin(1_000_000, "uatom")],
+        })
+    );
+    assert_eq!(query_transfer(&deps, &env), None);
+}
+
+#[test]
+fn failed_transfers_without_recovery_are_parked_until_retried() {
+    let (mut deps, env) = setup(None);
+
+    for sequence in 1..MAX_IBC_TRANSFER_ATTEMPTS as u64 {
+        let res = timeout(&mut deps, &env, sequence);
+        reply_with_sequence(&mut deps, &env, &res, sequence + 1);
+    }
+    let res = timeout(&mut deps, &env, MAX_IBC_TRANSFER_ATTEMPTS as u64);
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        query_transfer(&deps, &env).unwrap().status,
+        IbcTransferStatus::Failed {}
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::RetryIbcTransfer {
+            offer_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+    reply_with_sequence(&mut deps, &env, &res, 10);
+    assert_eq!(
+        query_transfer(&deps, &env).unwrap().attempts,
+        MAX_IBC_TRANSFER_ATTEMPTS + 1
+    );
+}
@@ -0,0 +1,239 @@
+use crate::helpers::{
+    assert_err, default_instantiate_msg, grant_authz, instantiate_contract_with_msg, query_balance,
+};
+use atomic_swaps_contract::error::ContractError::{
+    InaccurateFunds, OfferExpired, TooManyOpenOffers,
+};
+use atomic_swaps_contract::msg::{ExecuteMsg, InstantiateMsg, MakerLimits, QueryMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+fn make_offer_msg() -> ExecuteMsg {
+    ExecuteMsg::MakeOffer {
+        maker_coin: Coin::from(coin(1_000_000, "uatom")),
+        taker_coin: Coin::from(coin(1_000_000, "uosmo")),
+        referral: None,
+        access_gate: None,
+        taker_allowlist: None,
+        holding_requirements: None,
+        proceeds_recipient: None,
+        payout_split: None,
+        proceeds_action: None,
+        approval_window: None,
+        reveal_delay: None,
+        bond: None,
+    }
+}
+
+#[test]
+fn open_offers_are_capped_and_deposits_refunded_on_cancel() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+                coin(1_000_000_000_000, "uion"),
+            ],
+            2,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr =
+        instantiate_contract_with_msg(&wasm, admin, &default_instantiate_msg(admin));
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateMakerLimits {
+            limits: MakerLimits {
+                max_open_offers: Some(2),
+                min_offer_lifetime: 60,
+                deposit: Some(coin(1_000, "uion")),
+                ..MakerLimits::default()
+            },
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(&contract_addr, &make_offer_msg(), &[], maker)
+        .unwrap_err();
+    assert_err(err_res, InaccurateFunds {});
+
+    let ion_balance = query_balance(&bank, &maker.address(), "uion");
+    for _ in 0..2 {
+        wasm.execute(
+            &contract_addr,
+            &make_offer_msg(),
+            &[coin(1_000, "uion")],
+            maker,
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uion"),
+        ion_balance - 2_000
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &make_offer_msg(),
+            &[coin(1_000, "uion")],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(err_res, TooManyOpenOffers { max_open_offers: 2 });
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::CancelOffer {
+                offer_id: "1".to_string(),
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(err_res, "Offer can't be cancelled before");
+
+    app.increase_time(60);
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::CancelOffer {
+            offer_id: "1".to_string(),
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uion"),
+        ion_balance - 1_000
+    );
+
+    let open_offers: u32 = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::MakerOpenOffers {
+                maker: maker.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(open_offers, 1);
+}
+
+#[test]
+fn expired_offers_are_pruned_and_deposits_forfeited() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+                coin(1_000_000_000_000, "uion"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let treasury = &accs[3];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            fee_recipient: treasury.address(),
+            ..default_instantiate_msg(admin)
+        },
+    );
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateMakerLimits {
+            limits: MakerLimits {
+                deposit: Some(coin(1_000, "uion")),
+                offer_ttl: Some(100),
+                ..MakerLimits::default()
+            },
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &make_offer_msg(),
+        &[coin(1_000, "uion")],
+        maker,
+    )
+    .unwrap();
+
+    app.increase_time(100);
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::OfferTaker {
+                offer_id: "1".to_string(),
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, OfferExpired {});
+
+    let treasury_ion_balance = query_balance(&bank, &treasury.address(), "uion");
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::PruneExpiredOffers { limit: None },
+        &[],
+        taker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &treasury.address(), "uion"),
+        treasury_ion_balance + 1_000
+    );
+
+    let open_offers: u32 = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::MakerOpenOffers {
+                maker: maker.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(open_offers, 0);
+}
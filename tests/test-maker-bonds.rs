@@ -0,0 +1,182 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::error::ContractError::{MakerBondRequired, ModuleAccountPayout};
+use atomic_swaps_contract::msg::{
+    ExecuteMsg, MakerBondConfig, MakerBondEntry, MakerBondStatus, QueryMsg,
+};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+pub mod helpers;
+
+fn make_offer_msg(bond: Option<cosmwasm_std::Coin>) -> ExecuteMsg {
+    ExecuteMsg::from(MakeOffer {
+        bond,
+        ..Default::default()
+    })
+}
+
+// Module account of the distribution module, which bank refuses to send to
+const MODULE_ACCOUNT: &str = "osmo1jv65s3grqf6v6jl3dp4t6c9t9rk99cd80yhvld";
+
+fn take_msg(recipient: Option<&str>) -> ExecuteMsg {
+    ExecuteMsg::from(TakeOffer {
+        recipient: recipient.map(str::to_string),
+        ..Default::default()
+    })
+}
+
+fn query_bonds(
+    wasm: &Wasm<OsmosisTestApp>,
+    contract_addr: &str,
+    maker: &str,
+) -> Vec<MakerBondEntry> {
+    wasm.query(
+        contract_addr,
+        &QueryMsg::MakerBonds {
+            maker: maker.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap()
+}
+
+fn setup(app: &OsmosisTestApp) -> (Vec<SigningAccount>, String) {
+    let wasm = Wasm::new(app);
+    let (accs, contract_addr) = setup_contract(app, 3);
+    let admin = &accs[2];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateMakerBondConfig {
+            config: MakerBondConfig {
+                required_bond: Some(coin(1_000, "uion")),
+                slash_bps: 5_000,
+            },
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    (accs, contract_addr)
+}
+
+#[test]
+fn bonds_are_required_and_returned_on_settlement() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup(&app);
+    let maker = &accs[0];
+    let taker = &accs[1];
+
+    let err_res = wasm
+        .execute(&contract_addr, &make_offer_msg(None), &[], maker)
+        .unwrap_err();
+    assert_err(
+        err_res,
+        MakerBondRequired {
+            bond: coin(1_000, "uion"),
+        },
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &make_offer_msg(Some(coin(1_000, "uion"))),
+        &[coin(1_000, "uion")],
+        maker,
+    )
+    .unwrap();
+    let maker_balance = query_balance(&bank, &maker.address(), "uion");
+
+    wasm.execute(
+        &contract_addr,
+        &take_msg(None),
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uion"),
+        maker_balance + 1_000
+    );
+    assert_eq!(
+        query_bonds(&wasm, &contract_addr, &maker.address())[0].status,
+        MakerBondStatus::Returned {}
+    );
+}
+
+#[test]
+fn bonds_are_slashed_when_the_maker_coin_cannot_be_pulled() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup(&app);
+    let maker = &accs[0];
+    let taker = &accs[1];
+
+    wasm.execute(
+        &contract_addr,
+        &make_offer_msg(Some(coin(1_000, "uion"))),
+        &[coin(1_000, "uion")],
+        maker,
+    )
+    .unwrap();
+
+    // Takers can't have settlement fail on their side by paying out to a module account
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_msg(Some(MODULE_ACCOUNT)),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        ModuleAccountPayout {
+            address: MODULE_ACCOUNT.to_string(),
+        },
+    );
+
+    // The maker moves the offered coin away after making the offer
+    bank.send(
+        MsgSend {
+            from_address: maker.address(),
+            to_address: accs[2].address(),
+            amount: vec![Coin::from(coin(1_000_000_000_000, "uatom"))],
+        },
+        maker,
+    )
+    .unwrap();
+
+    let maker_balance = query_balance(&bank, &maker.address(), "uion");
+    let taker_balance = query_balance(&bank, &taker.address(), "uion");
+    let taker_atom = query_balance(&bank, &taker.address(), "uatom");
+    wasm.execute(
+        &contract_addr,
+        &take_msg(None),
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    assert_eq!(query_balance(&bank, &taker.address(), "uatom"), taker_atom);
+    assert_eq!(
+        query_balance(&bank, &taker.address(), "uion"),
+        taker_balance + 500
+    );
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uion"),
+        maker_balance + 500
+    );
+    assert_eq!(
+        query_bonds(&wasm, &contract_addr, &maker.address())[0].status,
+        MakerBondStatus::Slashed {
+            compensation: coin(500, "uion"),
+        }
+    );
+}
@@ -0,0 +1,280 @@
+use crate::helpers::{assert_err, grant_authz, instantiate_contract};
+use atomic_swaps_contract::error::ContractError::{
+    BelowMinimumSize, DenomNotAllowed, IdenticalDenoms, InvalidLotSize, InvalidTickSize,
+    MarketDisabled, ZeroAmount,
+};
+use atomic_swaps_contract::msg::{ExecuteMsg, Market, MarketsResponse, QueryMsg};
+use cosmwasm_std::{coin, Uint128};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Module, OsmosisTestApp, SigningAccount, Wasm};
+
+pub mod helpers;
+
+#[allow(clippy::result_large_err)]
+fn make_offer(
+    wasm: &Wasm<OsmosisTestApp>,
+    contract_addr: &str,
+    maker: &SigningAccount,
+    maker_coin: cosmwasm_std::Coin,
+    taker_coin: cosmwasm_std::Coin,
+) -> Result<(), osmosis_test_tube::RunnerError> {
+    wasm.execute(
+        contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(maker_coin),
+            taker_coin: Coin::from(taker_coin),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn offers_are_checked_against_market_rules() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            2,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    // Basic checks apply without any market configured
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(0, "uatom"),
+        coin(1_000, "uosmo"),
+    )
+    .unwrap_err();
+    assert_err(err_res, ZeroAmount {});
+
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_000, "uatom"),
+        coin(1_000, "uatom"),
+    )
+    .unwrap_err();
+    assert_err(err_res, IdenticalDenoms {});
+
+    let market = Market {
+        maker_denom: "uatom".to_string(),
+        taker_denom: "uosmo".to_string(),
+        enabled: true,
+        min_maker_amount: Uint128::new(1_000_000),
+        lot_size: Uint128::new(100_000),
+        tick_size: Uint128::new(50),
+    };
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateDenoms {
+            allow: vec!["uatom".to_string(), "uosmo".to_string()],
+            block: vec![],
+            unlist: vec![],
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateMarkets {
+            restricted: Some(true),
+            markets: vec![market.clone()],
+            remove: vec![],
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    let markets: MarketsResponse = wasm.query(&contract_addr, &QueryMsg::Markets {}).unwrap();
+    assert_eq!(
+        markets,
+        MarketsResponse {
+            restricted: true,
+            allowed_denoms: vec!["uatom".to_string(), "uosmo".to_string()],
+            blocked_denoms: vec![],
+            markets: vec![market],
+        }
+    );
+
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(100_000, "uatom"),
+        coin(100_000, "uosmo"),
+    )
+    .unwrap_err();
+    assert_err(
+        err_res,
+        BelowMinimumSize {
+            min_maker_amount: Uint128::new(1_000_000),
+        },
+    );
+
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_050_000, "uatom"),
+        coin(1_050_000, "uosmo"),
+    )
+    .unwrap_err();
+    assert_err(
+        err_res,
+        InvalidLotSize {
+            lot_size: Uint128::new(100_000),
+        },
+    );
+
+    // 10 lots at 10_010 uosmo per lot is not a multiple of the 50 uosmo tick
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_000_000, "uatom"),
+        coin(100_100, "uosmo"),
+    )
+    .unwrap_err();
+    assert_err(
+        err_res,
+        InvalidTickSize {
+            tick_size: Uint128::new(50),
+        },
+    );
+
+    make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_000_000, "uatom"),
+        coin(100_500, "uosmo"),
+    )
+    .unwrap();
+
+    // The reverse pair has no market and restricted markets only trade enabled pairs
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_000_000, "uosmo"),
+        coin(1_000_000, "uatom"),
+    )
+    .unwrap_err();
+    assert_err(
+        err_res,
+        MarketDisabled {
+            maker_denom: "uosmo".to_string(),
+            taker_denom: "uatom".to_string(),
+        },
+    );
+
+    let err_res = make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_000_000, "uatom"),
+        coin(1_000_000, "uion"),
+    )
+    .unwrap_err();
+    assert_err(
+        err_res,
+        DenomNotAllowed {
+            denom: "uion".to_string(),
+        },
+    );
+}
+
+#[test]
+fn blocked_denom_cannot_be_taken() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            3,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = instantiate_contract(&wasm, admin);
+
+    make_offer(
+        &wasm,
+        &contract_addr,
+        maker,
+        coin(1_000_000, "uatom"),
+        coin(1_000_000, "uosmo"),
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateDenoms {
+            allow: vec![],
+            block: vec!["uatom".to_string()],
+            unlist: vec![],
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::OfferTaker {
+                offer_id: "1".to_string(),
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        DenomNotAllowed {
+            denom: "uatom".to_string(),
+        },
+    );
+}
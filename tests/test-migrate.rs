@@ -0,0 +1,252 @@
+use crate::helpers::{
+    assert_err, default_instantiate_msg, grant_authz, migrate_contract, query_balance,
+    v1_wasm_file, wasm_file,
+};
+use atomic_swaps_contract::error::ContractError::{MigrationInProgress, MissingMigrationConfig};
+use atomic_swaps_contract::msg::{ExecuteMsg, MigrateMsg, MigrationProgress, QueryMsg};
+use cosmwasm_std::coin;
+use cw2::ContractVersion;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, SigningAccount, Wasm};
+use serde_json::json;
+
+pub mod helpers;
+
+// Instantiates v1 and makes `offers` offers of 1_000_000 uatom for 1_000_000 uosmo through it
+fn setup_v1_offers(
+    app: &OsmosisTestApp,
+    wasm: &Wasm<OsmosisTestApp>,
+    admin: &SigningAccount,
+    maker: &SigningAccount,
+    offers: u32,
+) -> String {
+    let code_id = wasm
+        .store_code(&v1_wasm_file(), None, admin)
+        .unwrap()
+        .data
+        .code_id;
+    let contract_addr = wasm
+        .instantiate(
+            code_id,
+            &json!({}),
+            Some(&admin.address()),
+            Some("atomic-swaps-contract"),
+            &[],
+            admin,
+        )
+        .unwrap()
+        .data
+        .address;
+    grant_authz(
+        app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    for _ in 0..offers {
+        wasm.execute(
+            &contract_addr,
+            &json!({
+                "make_offer": {
+                    "maker_coin": Coin::from(coin(1_000_000, "uatom")),
+                    "taker_coin": Coin::from(coin(1_000_000, "uosmo")),
+                }
+            }),
+            &[],
+            maker,
+        )
+        .unwrap();
+    }
+
+    contract_addr
+}
+
+#[test]
+fn v1_offers_are_filled_after_migration() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            3,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = setup_v1_offers(&app, &wasm, admin, maker, 2);
+
+    let code_id = wasm
+        .store_code(&wasm_file(), None, admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    let err_res = migrate_contract(
+        &app,
+        admin,
+        &contract_addr,
+        code_id,
+        &MigrateMsg {
+            config: None,
+            batch_size: None,
+        },
+    )
+    .unwrap_err();
+    assert_err(err_res, MissingMigrationConfig {});
+
+    migrate_contract(
+        &app,
+        admin,
+        &contract_addr,
+        code_id,
+        &MigrateMsg {
+            config: Some(default_instantiate_msg(admin)),
+            batch_size: None,
+        },
+    )
+    .unwrap();
+
+    let info: ContractVersion = wasm
+        .query(&contract_addr, &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(info.version, "0.2.0");
+
+    let progress: Option<MigrationProgress> = wasm
+        .query(&contract_addr, &QueryMsg::MigrationProgress {})
+        .unwrap();
+    assert_eq!(progress, None);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "2".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    let maker_osmo_balance = query_balance(&bank, &maker.address(), "uosmo");
+    let taker_atom_balance = query_balance(&bank, &taker.address(), "uatom");
+    assert_eq!(maker_osmo_balance, 1_000_001_000_000);
+    assert_eq!(taker_atom_balance, 1_000_001_000_000);
+}
+
+#[test]
+fn large_store_is_migrated_in_batches() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            3,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = setup_v1_offers(&app, &wasm, admin, maker, 3);
+
+    let code_id = wasm
+        .store_code(&wasm_file(), None, admin)
+        .unwrap()
+        .data
+        .code_id;
+    migrate_contract(
+        &app,
+        admin,
+        &contract_addr,
+        code_id,
+        &MigrateMsg {
+            config: Some(default_instantiate_msg(admin)),
+            batch_size: Some(2),
+        },
+    )
+    .unwrap();
+
+    let progress: Option<MigrationProgress> = wasm
+        .query(&contract_addr, &QueryMsg::MigrationProgress {})
+        .unwrap();
+    assert_eq!(
+        progress,
+        Some(MigrationProgress {
+            store: 0,
+            last_key: Some("2".to_string()),
+            migrated: 2,
+        })
+    );
+
+    // Offers can't be taken until every offer is in the new layout
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::OfferTaker {
+                offer_id: "1".to_string(),
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, MigrationInProgress {});
+
+    // Nor cancelled, since a re-encoded offer going away would break the migrated count
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::CancelOffer {
+                offer_id: "1".to_string(),
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(err_res, MigrationInProgress {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::ContinueMigration { limit: None },
+        &[],
+        taker,
+    )
+    .unwrap();
+
+    let progress: Option<MigrationProgress> = wasm
+        .query(&contract_addr, &QueryMsg::MigrationProgress {})
+        .unwrap();
+    assert_eq!(progress, None);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+}
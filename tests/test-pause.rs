@@ -0,0 +1,284 @@
+use crate::helpers::{
+    assert_err, default_instantiate_msg, grant_authz, instantiate_contract_with_msg,
+};
+use atomic_swaps_contract::error::ContractError::{NotOwner, NotOwnerOrGuardian, Paused};
+use atomic_swaps_contract::msg::{ExecuteMsg, InstantiateMsg, Offer, Operation, Pause, QueryMsg};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn guardian_pauses_taking_for_a_denom() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            4,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let guardian = &accs[3];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            guardian: Some(guardian.address()),
+            ..default_instantiate_msg(admin)
+        },
+    );
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::SetPause {
+                operation: Operation::Take,
+                denom: Some("uatom".to_string()),
+                paused: true,
+            },
+            &[],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwnerOrGuardian {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetPause {
+            operation: Operation::Take,
+            denom: Some("uatom".to_string()),
+            paused: true,
+        },
+        &[],
+        guardian,
+    )
+    .unwrap();
+
+    let pauses: Vec<Pause> = wasm.query(&contract_addr, &QueryMsg::Pauses {}).unwrap();
+    assert_eq!(
+        pauses,
+        vec![Pause {
+            operation: Operation::Take,
+            denom: Some("uatom".to_string()),
+        }]
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::OfferTaker {
+                offer_id: "1".to_string(),
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
+            &[coin(1_000_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        Paused {
+            operation: Operation::Take,
+        },
+    );
+
+    // Making offers is paused independently, so it keeps working
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    // Only the owner can lift the pause
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::SetPause {
+                operation: Operation::Take,
+                denom: Some("uatom".to_string()),
+                paused: false,
+            },
+            &[],
+            guardian,
+        )
+        .unwrap_err();
+    assert_err(err_res, NotOwner {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetPause {
+            operation: Operation::Take,
+            denom: Some("uatom".to_string()),
+            paused: false,
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+}
+
+#[test]
+fn maker_cancels_while_paused() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uatom")], 2)
+        .unwrap();
+    let maker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr =
+        instantiate_contract_with_msg(&wasm, admin, &default_instantiate_msg(admin));
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    for operation in [Operation::Make, Operation::Take, Operation::Settle] {
+        wasm.execute(
+            &contract_addr,
+            &ExecuteMsg::SetPause {
+                operation,
+                denom: None,
+                paused: true,
+            },
+            &[],
+            admin,
+        )
+        .unwrap();
+    }
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::MakeOffer {
+                maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+                taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+                referral: None,
+                access_gate: None,
+                taker_allowlist: None,
+                holding_requirements: None,
+                proceeds_recipient: None,
+                payout_split: None,
+                proceeds_action: None,
+                approval_window: None,
+                reveal_delay: None,
+                bond: None,
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        Paused {
+            operation: Operation::Make,
+        },
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::CancelOffer {
+            offer_id: "1".to_string(),
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let offers: Vec<Offer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AllOffers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(offers, vec![]);
+}
@@ -0,0 +1,79 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::error::ContractError::InvalidPayoutSplit;
+use atomic_swaps_contract::msg::{ExecuteMsg, Payout};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+fn make_offer_msg(payout_split: Vec<Payout>) -> ExecuteMsg {
+    ExecuteMsg::from(MakeOffer {
+        taker_coin: Coin::from(coin(1_000_001, "uosmo")),
+        payout_split: Some(payout_split),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn proceeds_are_split_by_weight() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let recipients = (0..3)
+        .map(|_| app.init_account(&[]).unwrap())
+        .collect::<Vec<_>>();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &make_offer_msg(vec![
+                Payout {
+                    address: recipients[0].address(),
+                    weight: 1,
+                },
+                Payout {
+                    address: recipients[0].address(),
+                    weight: 1,
+                },
+            ]),
+            &[],
+            maker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        InvalidPayoutSplit {
+            reason: format!("{} is listed twice", recipients[0].address()),
+        },
+    );
+
+    let split = recipients
+        .iter()
+        .zip([70, 20, 10])
+        .map(|(recipient, weight)| Payout {
+            address: recipient.address(),
+            weight,
+        })
+        .collect();
+    wasm.execute(&contract_addr, &make_offer_msg(split), &[], maker)
+        .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(TakeOffer::default()),
+        &[coin(1_000_001, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    // The unit left over after rounding goes to the share that lost the most to rounding
+    let balances = recipients
+        .iter()
+        .map(|recipient| query_balance(&bank, &recipient.address(), "uosmo"))
+        .collect::<Vec<_>>();
+    assert_eq!(balances, vec![700_001, 200_000, 100_000]);
+}
@@ -0,0 +1,125 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::error::ContractError::CallbackWithRecipient;
+use atomic_swaps_contract::msg::{ExecuteMsg, Offer, QueryMsg, TakerCallback};
+use cosmwasm_std::{coin, to_binary};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn proceeds_go_to_the_recipient_overrides() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let cold_storage = app.init_account(&[]).unwrap();
+    let client = app.init_account(&[]).unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer {
+            taker_coin: Coin::from(coin(2_000_000, "uosmo")),
+            proceeds_recipient: Some(cold_storage.address()),
+            ..Default::default()
+        }),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::from(TakeOffer {
+                recipient: Some(client.address()),
+                ..Default::default()
+            }),
+            &[coin(2_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap();
+
+    assert_eq!(
+        query_balance(&bank, &cold_storage.address(), "uosmo"),
+        2_000_000
+    );
+    assert_eq!(query_balance(&bank, &client.address(), "uatom"), 1_000_000);
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.attributes.iter().any(
+            |attr| attr.key == "taker_coin_recipient" && attr.value == cold_storage.address()
+        )));
+
+    let fulfilled: Offer = wasm
+        .query(&contract_addr, &QueryMsg::FulfilledOffers { offer_id: 1 })
+        .unwrap();
+    assert_eq!(fulfilled.taker, Some(taker.address()));
+    assert_eq!(fulfilled.proceeds_recipient, Some(cold_storage.address()));
+    assert_eq!(fulfilled.taker_recipient, Some(client.address()));
+}
+
+#[test]
+fn takes_with_unusable_recipients_are_refused() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let (accs, contract_addr) = setup_contract(&app, 3);
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let client = app.init_account(&[]).unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer::default()),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::from(TakeOffer {
+                recipient: Some("not-an-address".to_string()),
+                ..Default::default()
+            }),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, "addr_validate errored");
+
+    // Callbacks go to the taker, so the maker coin has to as well
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::from(TakeOffer {
+                recipient: Some(client.address()),
+                callback: Some(TakerCallback {
+                    msg: to_binary(&"swap").unwrap(),
+                    flash: false,
+                }),
+                ..Default::default()
+            }),
+            &[coin(1_000_000, "uosmo")],
+            taker,
+        )
+        .unwrap_err();
+    assert_err(err_res, CallbackWithRecipient {});
+
+    // Neither take touched the offer
+    let offers: Vec<Offer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AllOffers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(offers[0].taker, None);
+    assert_eq!(offers[0].taker_recipient, None);
+}
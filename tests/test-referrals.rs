@@ -0,0 +1,177 @@
+use crate::helpers::{
+    assert_err, default_instantiate_msg, grant_authz, instantiate_contract_with_msg, query_balance,
+};
+use atomic_swaps_contract::error::ContractError::ReferralFeeTooHigh;
+use atomic_swaps_contract::msg::{
+    ExecuteMsg, FeePayer, InstantiateMsg, QueryMsg, Referral, ReferralFeeSource,
+    ReferrerStatsResponse,
+};
+use cosmwasm_std::coin;
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn referral_fee_above_cap_is_rejected() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(&[coin(1_000_000_000_000, "uosmo")], 3)
+        .unwrap();
+    let maker = &accs[0];
+    let referrer = &accs[1];
+    let admin = &accs[2];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            fee_bps: 100,
+            max_referral_fee_bps: 50,
+            ..default_instantiate_msg(admin)
+        },
+    );
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::MakeOffer {
+                maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+                taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+                referral: Some(Referral {
+                    address: referrer.address(),
+                    fee_bps: 51,
+                }),
+                access_gate: None,
+                taker_allowlist: None,
+                holding_requirements: None,
+                proceeds_recipient: None,
+                payout_split: None,
+                proceeds_action: None,
+                approval_window: None,
+                reveal_delay: None,
+                bond: None,
+            },
+            &[],
+            maker,
+        )
+        .unwrap_err();
+
+    assert_err(
+        err_res,
+        ReferralFeeTooHigh {
+            fee_bps: 51,
+            max_fee_bps: 50,
+        },
+    )
+}
+
+#[test]
+fn referral_fees_are_carved_out_of_protocol_fee() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+            ],
+            6,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let treasury = &accs[3];
+    let maker_referrer = &accs[4];
+    let taker_referrer = &accs[5];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            fee_bps: 100,
+            fee_recipient: treasury.address(),
+            fee_payer: FeePayer::Maker,
+            max_referral_fee_bps: 50,
+            referral_fee_source: ReferralFeeSource::ProtocolFee,
+            ..default_instantiate_msg(admin)
+        },
+    );
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: Some(Referral {
+                address: maker_referrer.address(),
+                fee_bps: 30,
+            }),
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: Some(Referral {
+                address: taker_referrer.address(),
+                fee_bps: 50,
+            }),
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    // The 10_000_000 uosmo protocol fee is split 3_000_000 / 5_000_000 / 2_000_000
+    let maker_referrer_balance = query_balance(&bank, &maker_referrer.address(), "uosmo");
+    let taker_referrer_balance = query_balance(&bank, &taker_referrer.address(), "uosmo");
+    let treasury_balance = query_balance(&bank, &treasury.address(), "uosmo");
+    assert_eq!(maker_referrer_balance, 1_000_003_000_000);
+    assert_eq!(taker_referrer_balance, 1_000_005_000_000);
+    assert_eq!(treasury_balance, 1_000_002_000_000);
+
+    let stats: ReferrerStatsResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::ReferrerStats {
+                referrer: maker_referrer.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        stats,
+        ReferrerStatsResponse {
+            referrer: maker_referrer.address(),
+            referred_trades: 1,
+            earned: vec![Coin::from(coin(3_000_000, "uosmo"))],
+        }
+    );
+}
@@ -0,0 +1,142 @@
+use crate::helpers::{assert_err, query_balance, setup_contract, MakeOffer, TakeOffer};
+use atomic_swaps_contract::error::ContractError::OfferReserved;
+use atomic_swaps_contract::msg::{ExecuteMsg, Offer, QueryMsg, ReservationConfig};
+use cosmwasm_std::coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+pub mod helpers;
+
+fn take_msg() -> ExecuteMsg {
+    ExecuteMsg::from(TakeOffer::default())
+}
+
+fn setup(app: &OsmosisTestApp) -> (Vec<SigningAccount>, String) {
+    let wasm = Wasm::new(app);
+    let (accs, contract_addr) = setup_contract(app, 4);
+    let maker = &accs[0];
+    let admin = &accs[3];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::UpdateReservationConfig {
+            config: Some(ReservationConfig {
+                deposit: coin(1_000, "uion"),
+                max_blocks: 10,
+            }),
+        },
+        &[],
+        admin,
+    )
+    .unwrap();
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::from(MakeOffer::default()),
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    (accs, contract_addr)
+}
+
+#[test]
+fn only_the_reserver_takes_and_gets_the_deposit_back() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup(&app);
+    let reserver = &accs[1];
+    let other_taker = &accs[2];
+
+    let reserver_balance = query_balance(&bank, &reserver.address(), "uion");
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::ReserveOffer {
+            offer_id: "1".to_string(),
+            blocks: 10,
+        },
+        &[coin(1_000, "uion")],
+        reserver,
+    )
+    .unwrap();
+
+    let offers: Vec<Offer> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::AllOffers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let reservation = offers[0].reservation.clone().unwrap();
+    assert_eq!(reservation.reserver, reserver.address());
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &take_msg(),
+            &[coin(1_000_000, "uosmo")],
+            other_taker,
+        )
+        .unwrap_err();
+    assert_err(
+        err_res,
+        OfferReserved {
+            reserver: reserver.address(),
+            expires_at_height: reservation.expires_at_height,
+        },
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &take_msg(),
+        &[coin(1_000_000, "uosmo")],
+        reserver,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &reserver.address(), "uion"),
+        reserver_balance
+    );
+    let fulfilled: Offer = wasm
+        .query(&contract_addr, &QueryMsg::FulfilledOffers { offer_id: 1 })
+        .unwrap();
+    assert_eq!(fulfilled.taker, Some(reserver.address()));
+}
+
+#[test]
+fn lapsed_reservations_are_forfeited_to_the_maker() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+    let (accs, contract_addr) = setup(&app);
+    let maker = &accs[0];
+    let reserver = &accs[1];
+    let other_taker = &accs[2];
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::ReserveOffer {
+            offer_id: "1".to_string(),
+            blocks: 1,
+        },
+        &[coin(1_000, "uion")],
+        reserver,
+    )
+    .unwrap();
+
+    // Every transaction is in a block of its own, so the reservation has lapsed by the take
+    let maker_balance = query_balance(&bank, &maker.address(), "uion");
+    wasm.execute(
+        &contract_addr,
+        &take_msg(),
+        &[coin(1_000_000, "uosmo")],
+        other_taker,
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance(&bank, &maker.address(), "uion"),
+        maker_balance + 1_000
+    );
+}
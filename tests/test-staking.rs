@@ -0,0 +1,218 @@
+use crate::helpers::{
+    assert_err, default_instantiate_msg, grant_authz, instantiate_contract_with_msg, query_balance,
+};
+use atomic_swaps_contract::error::ContractError::{InsufficientBond, NothingToClaim};
+use atomic_swaps_contract::msg::{
+    ExecuteMsg, FeePayer, InstantiateMsg, QueryMsg, StakerResponse, StakingToken,
+};
+use cosmwasm_std::{coin, Uint128};
+use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_test_tube::{Account, Bank, Module, OsmosisTestApp, Wasm};
+
+pub mod helpers;
+
+#[test]
+fn settlement_fees_accrue_to_stakers() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let bank = Bank::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uatom"),
+                coin(1_000_000_000_000, "uosmo"),
+                coin(1_000_000_000_000, "uion"),
+            ],
+            5,
+        )
+        .unwrap();
+    let maker = &accs[0];
+    let taker = &accs[1];
+    let admin = &accs[2];
+    let staker_a = &accs[3];
+    let staker_b = &accs[4];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            fee_bps: 100,
+            fee_payer: FeePayer::Maker,
+            staking_token: Some(StakingToken::Native {
+                denom: "uion".to_string(),
+            }),
+            ..default_instantiate_msg(admin)
+        },
+    );
+    grant_authz(
+        &app,
+        maker,
+        &contract_addr,
+        Coin::from(coin(1_000_000_000_000_000, "uatom")),
+    );
+
+    // Staker A bonds three times as much as staker B
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::Bond {},
+        &[coin(3_000_000, "uion")],
+        staker_a,
+    )
+    .unwrap();
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::Bond {},
+        &[coin(1_000_000, "uion")],
+        staker_b,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::MakeOffer {
+            maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
+            taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
+        },
+        &[],
+        maker,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::OfferTaker {
+            offer_id: "1".to_string(),
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
+        &[coin(1_000_000_000, "uosmo")],
+        taker,
+    )
+    .unwrap();
+
+    // The 10_000_000 uosmo fee stays in the contract for stakers instead of going to the admin
+    let contract_osmo_balance = query_balance(&bank, &contract_addr, "uosmo");
+    assert_eq!(contract_osmo_balance, 10_000_000);
+
+    let pending_a: Vec<Coin> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::PendingRewards {
+                staker: staker_a.address(),
+            },
+        )
+        .unwrap();
+    let pending_b: Vec<Coin> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::PendingRewards {
+                staker: staker_b.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(pending_a, vec![Coin::from(coin(7_500_000, "uosmo"))]);
+    assert_eq!(pending_b, vec![Coin::from(coin(2_500_000, "uosmo"))]);
+
+    wasm.execute(&contract_addr, &ExecuteMsg::ClaimRewards {}, &[], staker_a)
+        .unwrap();
+
+    let contract_osmo_balance = query_balance(&bank, &contract_addr, "uosmo");
+    assert_eq!(contract_osmo_balance, 2_500_000);
+
+    let pending_a: Vec<Coin> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::PendingRewards {
+                staker: staker_a.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(pending_a, vec![]);
+
+    let err_res = wasm
+        .execute(&contract_addr, &ExecuteMsg::ClaimRewards {}, &[], staker_a)
+        .unwrap_err();
+    assert_err(err_res, NothingToClaim {});
+}
+
+#[test]
+fn staker_unbonds_bonded_tokens() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app
+        .init_accounts(
+            &[
+                coin(1_000_000_000_000, "uosmo"),
+                coin(1_000_000_000_000, "uion"),
+            ],
+            2,
+        )
+        .unwrap();
+    let staker = &accs[0];
+    let admin = &accs[1];
+
+    let contract_addr = instantiate_contract_with_msg(
+        &wasm,
+        admin,
+        &InstantiateMsg {
+            staking_token: Some(StakingToken::Native {
+                denom: "uion".to_string(),
+            }),
+            ..default_instantiate_msg(admin)
+        },
+    );
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::Bond {},
+        &[coin(1_000_000, "uion")],
+        staker,
+    )
+    .unwrap();
+
+    let err_res = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::Unbond {
+                amount: Uint128::new(1_000_001),
+            },
+            &[],
+            staker,
+        )
+        .unwrap_err();
+    assert_err(err_res, InsufficientBond {});
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::Unbond {
+            amount: Uint128::new(400_000),
+        },
+        &[],
+        staker,
+    )
+    .unwrap();
+
+    let response: StakerResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Staker {
+                address: staker.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(response.bonded, Uint128::new(600_000));
+}
@@ -42,6 +42,16 @@ fn maker_attempts_to_send_funds_before_accepted_match() {
             &ExecuteMsg::MakeOffer {
                 maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
                 taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+                referral: None,
+                access_gate: None,
+                taker_allowlist: None,
+                holding_requirements: None,
+                proceeds_recipient: None,
+                payout_split: None,
+                proceeds_action: None,
+                approval_window: None,
+                reveal_delay: None,
+                bond: None,
             },
             &[coin(1_000_000_000, "uatom")],
             &maker,
@@ -137,6 +147,16 @@ fn account_that_isnt_contract_attempts_to_directly_execute_fulfill_offer() {
         &ExecuteMsg::MakeOffer {
             maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
             taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
         },
         &[],
         &maker,
@@ -242,6 +262,16 @@ fn invalid_offer_id_is_passed() {
         &ExecuteMsg::MakeOffer {
             maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
             taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
         },
         &[],
         &maker,
@@ -255,7 +285,13 @@ fn invalid_offer_id_is_passed() {
     let err_res = wasm
         .execute(
             &contract_addr,
-            &ExecuteMsg::OfferTaker { offer_id },
+            &ExecuteMsg::OfferTaker {
+                offer_id,
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
             &[coin(1_000_000_000, "uatom")],
             &(taker),
         )
@@ -289,6 +325,16 @@ fn contract_never_authorized_by_maker() {
         &ExecuteMsg::MakeOffer {
             maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
             taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
         },
         &[],
         &maker,
@@ -302,7 +348,13 @@ fn contract_never_authorized_by_maker() {
     let res_err = wasm
         .execute(
             &contract_addr,
-            &ExecuteMsg::OfferTaker { offer_id },
+            &ExecuteMsg::OfferTaker {
+                offer_id,
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
             &[coin(1_000_000_000, "uosmo")],
             &(taker),
         )
@@ -395,6 +447,16 @@ fn additional_funds_are_sent_by_taker() {
         &ExecuteMsg::MakeOffer {
             maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
             taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
         },
         &[],
         &maker,
@@ -408,7 +470,13 @@ fn additional_funds_are_sent_by_taker() {
     let err_res = wasm
         .execute(
             &contract_addr,
-            &ExecuteMsg::OfferTaker { offer_id },
+            &ExecuteMsg::OfferTaker {
+                offer_id,
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
             &[coin(1_000_000_000, "uosmo"), coin(1_000_000_000, "uatom")],
             &(taker),
         )
@@ -501,6 +569,16 @@ fn incorrect_funds_are_sent_by_taker() {
         &ExecuteMsg::MakeOffer {
             maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
             taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
         },
         &[],
         &maker,
@@ -514,7 +592,13 @@ fn incorrect_funds_are_sent_by_taker() {
     let err_res = wasm
         .execute(
             &contract_addr,
-            &ExecuteMsg::OfferTaker { offer_id },
+            &ExecuteMsg::OfferTaker {
+                offer_id,
+                referral: None,
+                allowlist_proof: None,
+                recipient: None,
+                callback: None,
+            },
             &[coin(1_000_000_000, "uatom")],
             &(taker),
         )
@@ -626,6 +710,16 @@ fn successful_swap() {
         &ExecuteMsg::MakeOffer {
             maker_coin: Coin::from(coin(1_000_000_000, "uatom")),
             taker_coin: Coin::from(coin(1_000_000_000, "uosmo")),
+            referral: None,
+            access_gate: None,
+            taker_allowlist: None,
+            holding_requirements: None,
+            proceeds_recipient: None,
+            payout_split: None,
+            proceeds_action: None,
+            approval_window: None,
+            reveal_delay: None,
+            bond: None,
         },
         &[],
         &maker,
@@ -665,7 +759,13 @@ fn successful_swap() {
 
     wasm.execute(
         &contract_addr,
-        &ExecuteMsg::OfferTaker { offer_id },
+        &ExecuteMsg::OfferTaker {
+            offer_id,
+            referral: None,
+            allowlist_proof: None,
+            recipient: None,
+            callback: None,
+        },
         &[coin(1_000_000_000, "uosmo")],
         &(taker),
     )